@@ -11,10 +11,11 @@
 //! [here](https://codeforces.com/apiHelp) (in the Authorization section).
 //!
 //! This crate solely uses `reqwest`'s blocking network client meaning that all
-//! requests made through this crate are also blocking. No client object is
-//! provided with this crate and thus no rate-limiting provided. This could
-//! also impact performance since a new `reqwest` client is created and
-//! destroyed with every network interaction.
+//! requests made through this crate are also blocking. Calling `.get(..)`
+//! directly on a command creates and destroys a new `reqwest` client with
+//! every network interaction. To reuse a single network client (and avoid
+//! passing credentials with every call), use
+//! [`CFClient`](client::CFClient) instead.
 //!
 //! # Usage
 //!
@@ -52,8 +53,8 @@
 //! ```
 
 mod obj;
-pub use obj::error::Error;
-pub use obj::{requests, responses};
+pub use obj::error::{CFFailureKind, Error};
+pub use obj::{client, requests, responses};
 
 #[cfg(test)]
 mod test;
@@ -0,0 +1,1845 @@
+//! Contains [`CFClient`], a reusable client which holds API credentials and a
+//! single network client for making many requests.
+
+use serde::de::DeserializeOwned;
+use std::collections::BTreeMap;
+#[cfg(feature = "use_testcase_fetcher")]
+use std::collections::HashSet;
+use std::fmt;
+use std::io::Read;
+#[cfg(feature = "use_testcase_fetcher")]
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime};
+
+use super::error::*;
+use super::requests::{self, CFAPIRequestable};
+use super::responses;
+
+/// Client for sending requests to the Codeforces API (and the testcases
+/// scraper) which stores the API key/secret and reuses one underlying
+/// [`reqwest::blocking::Client`] across requests.
+///
+/// # Examples
+///
+/// ```
+/// # use codeforces_api::client::CFClient;
+/// # use codeforces_api::requests::*;
+/// # use codeforces_api::responses::*;
+/// # let api_key = codeforces_api::TEST_API_KEY;
+/// # let api_secret = codeforces_api::TEST_API_SECRET;
+/// let client = CFClient::new(api_key, api_secret);
+/// let x = CFBlogEntryCommand::View {
+///     blog_entry_id: 82347,
+/// };
+///
+/// match client.get(&x) {
+///     Ok(CFResult::CFBlogEntry(e)) => {
+///         // your code here
+///     },
+///     _ => {
+///         panic!("API request failed");
+///     }
+/// }
+/// ```
+#[derive(Clone)]
+pub struct CFClient {
+    api_key: String,
+    api_secret: String,
+    client: reqwest::blocking::Client,
+    timeout: Option<Duration>,
+    connect_timeout: Option<Duration>,
+    pool_idle_timeout: Option<Duration>,
+    dry_run: bool,
+    min_interval: Option<Duration>,
+    last_request: Arc<Mutex<Option<Instant>>>,
+    api_url: String,
+    web_url: String,
+    https_only: bool,
+    known_tags: Arc<Mutex<Option<Vec<String>>>>,
+    deadline: Option<Instant>,
+    stats: Arc<StatsCounters>,
+    retries: Option<Retries>,
+    handle: Option<String>,
+    max_response_bytes: Option<usize>,
+    external_client: bool,
+    request_logger: Option<RequestLogger>,
+}
+
+/// Callback invoked with the url of every request sent by a [`CFClient`],
+/// along with whether the `apiSig` should be redacted before calling it.
+#[derive(Clone)]
+struct RequestLogger {
+    callback: Arc<dyn Fn(&str) + Send + Sync>,
+    redact_api_sig: bool,
+}
+
+/// Retry policy set by [`CFClient::with_retries`], along with the callback
+/// set by [`CFClient::with_on_retry`].
+#[derive(Clone)]
+struct Retries {
+    max_retries: u32,
+    base_delay: Duration,
+    on_retry: Option<Arc<RetryCallback>>,
+}
+
+/// Callback invoked before each retry with the attempt number, the error
+/// which caused the retry and the delay before retrying.
+type RetryCallback = dyn Fn(u32, &Error, Duration) + Send + Sync;
+
+/// Counters behind [`CFClientStats`], shared between a client and its clones.
+#[derive(Debug, Default)]
+struct StatsCounters {
+    requests: AtomicU64,
+    cache_hits: AtomicU64,
+    retries: AtomicU64,
+    failures: AtomicU64,
+}
+
+/// Snapshot of the usage counters of a [`CFClient`] (and its clones), as
+/// returned by [`CFClient::stats`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct CFClientStats {
+    /// Number of requests actually sent over the network (API and scraping).
+    /// Requests refused in dry-run mode or after the deadline are not
+    /// counted.
+    pub requests: u64,
+    /// Number of calls answered from a cache without sending a request (eg.
+    /// [`CFClient::known_tags`]).
+    pub cache_hits: u64,
+    /// Number of times a failed request was retried (see
+    /// [`CFClient::with_retries`]).
+    pub retries: u64,
+    /// Number of sent requests which failed, whether because of a network
+    /// error, an unparsable response or a `status: FAILED` response.
+    pub failures: u64,
+}
+
+impl fmt::Debug for CFClient {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CFClient")
+            .field("api_key", &self.api_key)
+            .field("client", &self.client)
+            .field("timeout", &self.timeout)
+            .field("connect_timeout", &self.connect_timeout)
+            .field("pool_idle_timeout", &self.pool_idle_timeout)
+            .field("dry_run", &self.dry_run)
+            .field("min_interval", &self.min_interval)
+            .field("api_url", &self.api_url)
+            .field("web_url", &self.web_url)
+            .field("https_only", &self.https_only)
+            .field("known_tags", &self.known_tags)
+            .field("deadline", &self.deadline)
+            .field("stats", &self.stats)
+            .field("retries", &self.retries.as_ref().map(|r| r.max_retries))
+            .field("handle", &self.handle)
+            .field("max_response_bytes", &self.max_response_bytes)
+            .field("external_client", &self.external_client)
+            .field("request_logger", &self.request_logger.is_some())
+            .finish_non_exhaustive()
+    }
+}
+
+impl CFClient {
+    /// Start building a client with [`CFClientBuilder`], for configuring
+    /// several options at once.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use codeforces_api::client::CFClient;
+    /// # use std::time::Duration;
+    /// # let api_key = codeforces_api::TEST_API_KEY;
+    /// # let api_secret = codeforces_api::TEST_API_SECRET;
+    /// let client = CFClient::builder()
+    ///     .credentials(api_key, api_secret)
+    ///     .timeout(Duration::from_secs(10))
+    ///     .min_interval(Duration::from_secs(2))
+    ///     .https_only(true)
+    ///     .build()
+    ///     .unwrap();
+    /// ```
+    pub fn builder() -> CFClientBuilder {
+        CFClientBuilder::default()
+    }
+
+    /// Create a new client which signs every request with the given API key
+    /// and secret.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the underlying network client cannot be built, like
+    /// [`reqwest::blocking::Client::new`]. Use [`CFClient::builder`] to
+    /// handle this as an error instead.
+    pub fn new(api_key: &str, api_secret: &str) -> CFClient {
+        CFClient::from_client(
+            CFClient::http_client(false, None, None, None).unwrap(),
+            api_key,
+            api_secret,
+        )
+    }
+
+    /// Create a client around the given network client, with every other
+    /// option left at its default.
+    fn from_client(
+        client: reqwest::blocking::Client,
+        api_key: &str,
+        api_secret: &str,
+    ) -> CFClient {
+        CFClient {
+            api_key: api_key.to_string(),
+            api_secret: api_secret.to_string(),
+            client,
+            timeout: None,
+            connect_timeout: None,
+            pool_idle_timeout: None,
+            dry_run: false,
+            min_interval: None,
+            last_request: Arc::new(Mutex::new(None)),
+            api_url: requests::API_STUB.to_string(),
+            web_url: requests::WEB_STUB.to_string(),
+            https_only: false,
+            known_tags: Arc::new(Mutex::new(None)),
+            deadline: None,
+            stats: Arc::new(StatsCounters::default()),
+            retries: None,
+            handle: None,
+            max_response_bytes: None,
+            external_client: false,
+            request_logger: None,
+        }
+    }
+
+    /// Create a new client which sends every request through an existing,
+    /// caller-configured network `client` (eg. with custom TLS settings, a
+    /// proxy or a cookie store), signing requests with the given API key and
+    /// secret.
+    ///
+    /// The given `client` is used as is: options which configure the network
+    /// client itself ([`with_timeout`](CFClient::with_timeout),
+    /// [`with_connect_timeout`](CFClient::with_connect_timeout),
+    /// [`with_pool_idle_timeout`](CFClient::with_pool_idle_timeout) and the
+    /// plain-HTTP refusal of [`with_https_only`](CFClient::with_https_only))
+    /// have no effect on it.
+    pub fn with_existing_client(
+        client: reqwest::blocking::Client,
+        api_key: &str,
+        api_secret: &str,
+    ) -> CFClient {
+        CFClient {
+            external_client: true,
+            ..CFClient::from_client(client, api_key, api_secret)
+        }
+    }
+
+    /// Overall timeout for each request, if one has been set.
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+
+    /// Set the overall timeout for each request, from connecting until the
+    /// response body has been read.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self.rebuild_http_client();
+        self
+    }
+
+    /// Set the timeout for only the connect phase of each request. This can
+    /// be shorter than the overall timeout set by
+    /// [`with_timeout`](CFClient::with_timeout) so that unreachable servers
+    /// fail fast while large pages can still be read slowly.
+    pub fn with_connect_timeout(mut self, connect_timeout: Duration) -> Self {
+        self.connect_timeout = Some(connect_timeout);
+        self.rebuild_http_client();
+        self
+    }
+
+    /// Set how long idle connections are kept alive in the client's
+    /// connection pool. Requests to the same host reuse pooled connections,
+    /// which avoids a new TCP (and TLS) handshake for each request. Defaults
+    /// to 90 seconds.
+    pub fn with_pool_idle_timeout(
+        mut self,
+        pool_idle_timeout: Duration,
+    ) -> Self {
+        self.pool_idle_timeout = Some(pool_idle_timeout);
+        self.rebuild_http_client();
+        self
+    }
+
+    /// Set the handle of the user owning the API key, as returned by
+    /// [`whoami`](CFClient::whoami).
+    pub fn with_handle(mut self, handle: &str) -> Self {
+        self.handle = Some(handle.to_string());
+        self
+    }
+
+    /// Limit the size of API response bodies to `max_response_bytes`. Reading
+    /// a larger response is aborted with [`Error::ResponseTooLarge`] rather
+    /// than buffering it whole, eg. when accidentally requesting all rated
+    /// users in a memory-constrained environment.
+    pub fn with_max_response_bytes(
+        mut self,
+        max_response_bytes: usize,
+    ) -> Self {
+        self.max_response_bytes = Some(max_response_bytes);
+        self
+    }
+
+    /// Set the base url which API requests are sent to, in place of
+    /// `https://codeforces.com/api/`. Useful, for example, for routing
+    /// requests through a proxy or a mirror.
+    ///
+    /// Returns [`Error::InvalidParameter`] if the client is set to be
+    /// HTTPS-only and `api_url` is not an `https://` url.
+    pub fn with_base_url(mut self, api_url: &str) -> Result<Self, Error> {
+        self.api_url = api_url.to_string();
+        if !self.api_url.ends_with('/') {
+            self.api_url.push('/');
+        }
+        self.check_https_only()?;
+        Ok(self)
+    }
+
+    /// Set the base url of the Codeforces website which scraping requests
+    /// (eg. fetching testcases) are sent to, in place of
+    /// `https://codeforces.com/`. Useful, for example, for routing them
+    /// through a mirror.
+    ///
+    /// Returns [`Error::InvalidParameter`] if the client is set to be
+    /// HTTPS-only and `web_url` is not an `https://` url.
+    pub fn with_web_url(mut self, web_url: &str) -> Result<Self, Error> {
+        self.web_url = web_url.to_string();
+        if !self.web_url.ends_with('/') {
+            self.web_url.push('/');
+        }
+        self.check_https_only()?;
+        Ok(self)
+    }
+
+    /// Enable or disable HTTPS-only mode. In HTTPS-only mode, the base url
+    /// and web url must be `https://` urls and the underlying network client refuses to
+    /// send any request over plain HTTP.
+    ///
+    /// Returns [`Error::InvalidParameter`] if enabling HTTPS-only mode while
+    /// the base url is not an `https://` url.
+    pub fn with_https_only(mut self, https_only: bool) -> Result<Self, Error> {
+        self.https_only = https_only;
+        self.check_https_only()?;
+        self.rebuild_http_client();
+        Ok(self)
+    }
+
+    /// Check that the base url is an `https://` url if HTTPS-only mode is
+    /// enabled.
+    fn check_https_only(&self) -> Result<(), Error> {
+        if self.https_only && !self.api_url.starts_with("https://") {
+            return Err(Error::InvalidParameter(
+                "base url must use https when https_only is set",
+            ));
+        }
+        if self.https_only && !self.web_url.starts_with("https://") {
+            return Err(Error::InvalidParameter(
+                "web url must use https when https_only is set",
+            ));
+        }
+        Ok(())
+    }
+
+    /// Rebuild the underlying network client after an option changed, unless
+    /// it was provided by the caller (see
+    /// [`with_existing_client`](CFClient::with_existing_client)).
+    ///
+    /// Panics if the network client cannot be built.
+    fn rebuild_http_client(&mut self) {
+        if !self.external_client {
+            self.client = self.build_http_client().unwrap();
+        }
+    }
+
+    /// Build the underlying network client from the configured options.
+    fn build_http_client(&self) -> Result<reqwest::blocking::Client, Error> {
+        CFClient::http_client(
+            self.https_only,
+            self.timeout,
+            self.connect_timeout,
+            self.pool_idle_timeout,
+        )
+    }
+
+    /// Build a network client with the given options. Connections are kept
+    /// alive (and pooled for `pool_idle_timeout`, 90 seconds by default) so
+    /// that consecutive requests to Codeforces reuse them.
+    fn http_client(
+        https_only: bool,
+        timeout: Option<Duration>,
+        connect_timeout: Option<Duration>,
+        pool_idle_timeout: Option<Duration>,
+    ) -> Result<reqwest::blocking::Client, Error> {
+        let mut builder = reqwest::blocking::Client::builder()
+            .https_only(https_only)
+            .tcp_keepalive(Duration::from_secs(60))
+            .pool_idle_timeout(
+                pool_idle_timeout.unwrap_or(Duration::from_secs(90)),
+            );
+        if let Some(d) = timeout {
+            builder = builder.timeout(d);
+        }
+        if let Some(d) = connect_timeout {
+            builder = builder.connect_timeout(d);
+        }
+        Ok(builder.build()?)
+    }
+
+    /// Register a callback which is invoked with the signed url of every
+    /// request (API or scraping) just before it is sent.
+    ///
+    /// If `redact_api_sig` is `true`, then the value of the `apiSig` query
+    /// parameter is replaced with `<redacted>` in the logged url. The API
+    /// secret itself is never part of the url.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use codeforces_api::client::CFClient;
+    /// # let api_key = codeforces_api::TEST_API_KEY;
+    /// # let api_secret = codeforces_api::TEST_API_SECRET;
+    /// let client = CFClient::new(api_key, api_secret)
+    ///     .with_request_logger(|url| println!("GET {}", url), true);
+    /// ```
+    pub fn with_request_logger<F>(
+        mut self,
+        logger: F,
+        redact_api_sig: bool,
+    ) -> Self
+    where
+        F: Fn(&str) + Send + Sync + 'static,
+    {
+        self.request_logger = Some(RequestLogger {
+            callback: Arc::new(logger),
+            redact_api_sig,
+        });
+        self
+    }
+
+    /// Set a minimum interval between consecutive requests sent by this
+    /// client (and its clones), sleeping before a request if needed.
+    ///
+    /// The interval is shared by API requests and scraping requests (eg.
+    /// fetching testcases), since both are sent to `codeforces.com`. The
+    /// Codeforces API allows at most one call every 2 seconds.
+    pub fn with_min_interval(mut self, min_interval: Duration) -> Self {
+        self.min_interval = Some(min_interval);
+        self
+    }
+
+    /// Retry requests which fail with a retryable error (see
+    /// [`Error::is_retryable`]) up to `max_retries` times, sleeping
+    /// `base_delay` before the first retry and doubling the delay before each
+    /// further retry.
+    pub fn with_retries(
+        mut self,
+        max_retries: u32,
+        base_delay: Duration,
+    ) -> Self {
+        let on_retry = self.retries.take().and_then(|r| r.on_retry);
+        self.retries = Some(Retries {
+            max_retries,
+            base_delay,
+            on_retry,
+        });
+        self
+    }
+
+    /// Register a callback which is invoked before each retry (see
+    /// [`with_retries`](CFClient::with_retries)) with the attempt number
+    /// (starting at 1), the error which caused the retry and the delay before
+    /// retrying. Useful for logging or updating a progress bar.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use codeforces_api::client::CFClient;
+    /// # use std::time::Duration;
+    /// # let api_key = codeforces_api::TEST_API_KEY;
+    /// # let api_secret = codeforces_api::TEST_API_SECRET;
+    /// let client = CFClient::new(api_key, api_secret)
+    ///     .with_retries(3, Duration::from_secs(2))
+    ///     .with_on_retry(|attempt, err, delay| {
+    ///         eprintln!("retry {} in {:?}: {}", attempt, delay, err)
+    ///     });
+    /// ```
+    pub fn with_on_retry<F>(mut self, on_retry: F) -> Self
+    where
+        F: Fn(u32, &Error, Duration) + Send + Sync + 'static,
+    {
+        let retries = self.retries.get_or_insert(Retries {
+            max_retries: 0,
+            base_delay: Duration::from_secs(0),
+            on_retry: None,
+        });
+        retries.on_retry = Some(Arc::new(on_retry));
+        self
+    }
+
+    /// Run `request`, retrying it according to the client's retry policy
+    /// while it fails with a retryable error.
+    fn retrying<T, F>(&self, mut request: F) -> Result<T, Error>
+    where
+        F: FnMut() -> Result<T, Error>,
+    {
+        let mut attempt = 0;
+        loop {
+            let res = request();
+            let retries = match (&res, &self.retries) {
+                (Err(e), Some(r))
+                    if e.is_retryable() && attempt < r.max_retries =>
+                {
+                    r
+                }
+                _ => return res,
+            };
+            attempt += 1;
+            let delay = retries.base_delay * 2u32.saturating_pow(attempt - 1);
+            if let (Some(on_retry), Err(e)) = (&retries.on_retry, &res) {
+                on_retry(attempt, e, delay);
+            }
+            self.stats.retries.fetch_add(1, Ordering::Relaxed);
+            thread::sleep(delay);
+        }
+    }
+
+    /// Set an overall deadline after which the client stops sending requests,
+    /// returning [`Error::DeadlineExceeded`] instead. Unlike
+    /// [`with_timeout`](CFClient::with_timeout), which bounds each request,
+    /// this bounds a whole run of requests. A request which would have to
+    /// wait for the [minimum interval](CFClient::with_min_interval) past the
+    /// deadline fails immediately rather than sleeping.
+    ///
+    /// Batch and pagination helpers stop once the deadline passes while
+    /// keeping what was already fetched: eg. [`get_all`](CFClient::get_all)
+    /// returns `Err(Error::DeadlineExceeded)` for each command it did not
+    /// send, and [`submissions_page_stream`](CFClient::submissions_page_stream)
+    /// yields it as its last item.
+    pub fn with_deadline(mut self, deadline: Instant) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+
+    /// Enable or disable dry-run mode. In dry-run mode no requests are sent;
+    /// instead, every request returns [`Error::DryRun`] carrying the signed
+    /// url which would have been fetched. This is useful for auditing or for
+    /// performing the HTTP request yourself.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use codeforces_api::client::CFClient;
+    /// # use codeforces_api::requests::*;
+    /// # use codeforces_api::Error;
+    /// # let api_key = codeforces_api::TEST_API_KEY;
+    /// # let api_secret = codeforces_api::TEST_API_SECRET;
+    /// let client = CFClient::new(api_key, api_secret).with_dry_run(true);
+    /// let x = CFUserCommand::Info {
+    ///     handles: vec!["thud".to_string()],
+    /// };
+    ///
+    /// if let Err(Error::DryRun(url)) = client.get(&x) {
+    ///     println!("would have fetched {}", url);
+    /// }
+    /// ```
+    pub fn with_dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    /// Send a GET request to `url` through the underlying network client,
+    /// logging it first if a request logger is registered.
+    pub(crate) fn send(
+        &self,
+        url: &str,
+    ) -> Result<reqwest::blocking::Response, Error> {
+        if let Some(ref logger) = self.request_logger {
+            if logger.redact_api_sig {
+                (logger.callback)(&redact_api_sig(url));
+            } else {
+                (logger.callback)(url);
+            }
+        }
+        if self.dry_run {
+            return Err(Error::DryRun(url.to_string()));
+        }
+        self.check_deadline()?;
+        self.wait_for_min_interval()?;
+        self.stats.requests.fetch_add(1, Ordering::Relaxed);
+        self.count_failure(self.client.get(url).send().map_err(Error::from))
+    }
+
+    /// Count `res` as a failed request if it is an error, then return it.
+    fn count_failure<T>(&self, res: Result<T, Error>) -> Result<T, Error> {
+        if res.is_err() {
+            self.stats.failures.fetch_add(1, Ordering::Relaxed);
+        }
+        res
+    }
+
+    /// Returns a snapshot of how many requests this client (and its clones)
+    /// has sent, how many calls were answered from a cache, and how many sent
+    /// requests failed.
+    pub fn stats(&self) -> CFClientStats {
+        CFClientStats {
+            requests: self.stats.requests.load(Ordering::Relaxed),
+            cache_hits: self.stats.cache_hits.load(Ordering::Relaxed),
+            retries: self.stats.retries.load(Ordering::Relaxed),
+            failures: self.stats.failures.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Returns [`Error::DeadlineExceeded`] if the client's deadline has
+    /// passed.
+    fn check_deadline(&self) -> Result<(), Error> {
+        match self.deadline {
+            Some(deadline) if Instant::now() >= deadline => {
+                Err(Error::DeadlineExceeded)
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Sleep until at least `min_interval` has passed since the previous
+    /// request, then mark a new request as started.
+    ///
+    /// Returns [`Error::DeadlineExceeded`] without sleeping if the deadline
+    /// would pass before the wait is over.
+    fn wait_for_min_interval(&self) -> Result<(), Error> {
+        let mut last_request = self.last_request.lock().unwrap();
+        if let (Some(min_interval), Some(last)) =
+            (self.min_interval, *last_request)
+        {
+            let elapsed = last.elapsed();
+            if elapsed < min_interval {
+                let wait = min_interval - elapsed;
+                if self.deadline.is_some_and(|d| Instant::now() + wait >= d) {
+                    return Err(Error::DeadlineExceeded);
+                }
+                thread::sleep(wait);
+            }
+        }
+        *last_request = Some(Instant::now());
+        Ok(())
+    }
+
+    /// Fetch response from Codeforces servers, equivalent to
+    /// [`CFAPIRequestable::get`] but using this client's credentials.
+    pub fn get<T: CFAPIRequestable + ?Sized>(
+        &self,
+        command: &T,
+    ) -> Result<responses::CFResult, Error> {
+        self.get_as(command, &self.api_key, &self.api_secret)
+    }
+
+    /// Analogous to [`CFClient::get`], but signs this one request with the
+    /// given credentials in place of the client's own. Useful for making
+    /// requests on behalf of several users with a single client.
+    pub fn get_as<T: CFAPIRequestable + ?Sized>(
+        &self,
+        command: &T,
+        api_key: &str,
+        api_secret: &str,
+    ) -> Result<responses::CFResult, Error> {
+        command.validate()?;
+        #[cfg(feature = "tracing")]
+        let span = requests::request_span(command.method_name());
+        #[cfg(feature = "tracing")]
+        let _enter = span.enter();
+        let url = requests::as_codeforces_api_url_with_stub(
+            &self.api_url,
+            command,
+            api_key,
+            api_secret,
+        );
+        let res = self.retrying(|| {
+            self.send(&url).and_then(|r| {
+                self.count_failure(self.read_text(r).and_then(|body| {
+                    requests::parse_codeforces_api_body(&body)
+                }))
+            })
+        });
+        #[cfg(feature = "tracing")]
+        requests::record_outcome(&span, &res);
+        res
+    }
+
+    /// Fetch raw JSON response from Codeforces servers, equivalent to
+    /// [`CFAPIRequestable::get_raw`] but using this client's credentials.
+    pub fn get_raw<T: CFAPIRequestable + ?Sized>(
+        &self,
+        command: &T,
+    ) -> Result<String, Error> {
+        command.validate()?;
+        let url = requests::as_codeforces_api_url_with_stub(
+            &self.api_url,
+            command,
+            &self.api_key,
+            &self.api_secret,
+        );
+        self.retrying(|| {
+            let res = self.send(&url)?;
+            self.count_failure(self.read_text(res))
+        })
+    }
+
+    /// Read the body of `res` as text, returning
+    /// [`Error::ResponseTooLarge`] as soon as it exceeds the client's maximum
+    /// response size, if any.
+    fn read_text(
+        &self,
+        res: reqwest::blocking::Response,
+    ) -> Result<String, Error> {
+        let max = match self.max_response_bytes {
+            Some(max) => max,
+            None => return Ok(res.text()?),
+        };
+        if res.content_length().is_some_and(|len| len > max as u64) {
+            return Err(Error::ResponseTooLarge(max));
+        }
+        let mut body = Vec::new();
+        res.take(max as u64 + 1).read_to_end(&mut body)?;
+        if body.len() > max {
+            return Err(Error::ResponseTooLarge(max));
+        }
+        Ok(String::from_utf8_lossy(&body).into_owned())
+    }
+
+    /// Fetch response from Codeforces servers, returning both the parsed
+    /// result and the raw JSON String it was parsed from. The request is only
+    /// sent once, which is useful for debugging parsing issues.
+    pub fn get_with_raw<T: CFAPIRequestable + ?Sized>(
+        &self,
+        command: &T,
+    ) -> Result<(responses::CFResult, String), Error> {
+        let raw = self.get_raw(command)?;
+        let res =
+            self.count_failure(requests::parse_codeforces_api_body(&raw))?;
+        Ok((res, raw))
+    }
+
+    /// Fetch response from Codeforces servers, returning the parsed result
+    /// along with the `comment` of the response, if any. Codeforces
+    /// occasionally sets a comment on successful responses (eg. deprecation
+    /// notices), which [`get`](CFClient::get) discards.
+    pub fn get_with_meta<T: CFAPIRequestable + ?Sized>(
+        &self,
+        command: &T,
+    ) -> Result<(responses::CFResult, Option<String>), Error> {
+        let raw = self.get_raw(command)?;
+        self.count_failure(requests::parse_codeforces_api_body_with_comment(
+            &raw,
+        ))
+    }
+
+    /// Fetch response from Codeforces servers and deserialize its `result`
+    /// into a caller-provided type `T`, rather than a [`responses::CFResult`].
+    ///
+    /// Useful for fields which this crate does not (yet) model, or for using
+    /// custom response structs. The response `status` is still checked, so a
+    /// failed request returns [`Error::CodeforcesApi`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use codeforces_api::client::CFClient;
+    /// # use codeforces_api::requests::*;
+    /// # let api_key = codeforces_api::TEST_API_KEY;
+    /// # let api_secret = codeforces_api::TEST_API_SECRET;
+    /// #[derive(serde::Deserialize)]
+    /// struct MinimalUser {
+    ///     handle: String,
+    /// }
+    ///
+    /// let client = CFClient::new(api_key, api_secret);
+    /// let x = CFUserCommand::Info {
+    ///     handles: vec!["thud".to_string()],
+    /// };
+    /// let users: Vec<MinimalUser> = client.get_into(&x).unwrap();
+    /// assert_eq!(users[0].handle, "thud");
+    /// ```
+    pub fn get_into<T, C>(&self, command: &C) -> Result<T, Error>
+    where
+        T: DeserializeOwned,
+        C: CFAPIRequestable + ?Sized,
+    {
+        let raw = self.get_raw(command)?;
+        self.count_failure(requests::parse_codeforces_api_body_into(&raw))
+    }
+
+    /// Fetch responses for several (possibly different kinds of) commands in
+    /// turn, respecting the client's minimum request interval.
+    ///
+    /// Returns one result per command, in the same order as `commands`. A
+    /// failed request does not stop the remaining commands from being sent.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use codeforces_api::client::CFClient;
+    /// # use codeforces_api::requests::*;
+    /// # let api_key = codeforces_api::TEST_API_KEY;
+    /// # let api_secret = codeforces_api::TEST_API_SECRET;
+    /// let client = CFClient::new(api_key, api_secret);
+    /// let blog = CFBlogEntryCommand::View {
+    ///     blog_entry_id: 82347,
+    /// };
+    /// let user = CFUserCommand::Info {
+    ///     handles: vec!["thud".to_string()],
+    /// };
+    ///
+    /// let results = client.get_all(&[&blog, &user]);
+    /// assert_eq!(results.len(), 2);
+    /// ```
+    pub fn get_all(
+        &self,
+        commands: &[&dyn CFAPIRequestable],
+    ) -> Vec<Result<responses::CFResult, Error>> {
+        commands.iter().map(|c| self.get(*c)).collect()
+    }
+
+    /// Scrape the input testcases of `problem` and write each one to a
+    /// numbered file `{dir}/{index}_{n}.txt`, creating `dir` if it is
+    /// missing.
+    ///
+    /// Returns the paths of the written files. Expected outputs are not
+    /// scraped, so only input files are written.
+    ///
+    /// See [`requests::write_testcases_to_dir`] for the file layout.
+    #[cfg(feature = "use_testcase_fetcher")]
+    pub fn download_testcases_to_dir<P: AsRef<Path>>(
+        &self,
+        problem: &responses::CFProblem,
+        dir: P,
+    ) -> Result<Vec<PathBuf>, Error> {
+        let bytes = self.scrape(&problem.page_url(&self.web_url)?)?;
+        let testcases = requests::parse_testcases(&bytes)?;
+        // `page_url` only succeeds if the index is set.
+        let index = problem.index.as_deref().unwrap_or_default();
+        requests::write_testcases_to_dir(dir, index, &testcases)
+    }
+
+    /// Scrape the input testcases of `problem`, setting its `input_testcases`
+    /// field on success.
+    ///
+    /// Same as [`CFProblem::fetch_testcases`](responses::CFProblem::fetch_testcases),
+    /// but the request is sent through this client, so it respects the
+    /// client's [`with_min_interval`](CFClient::with_min_interval).
+    #[cfg(feature = "use_testcase_fetcher")]
+    pub fn fetch_testcases(
+        &self,
+        problem: &mut responses::CFProblem,
+    ) -> Result<Vec<String>, Error> {
+        let testcases = self
+            .scrape(&problem.page_url(&self.web_url)?)
+            .and_then(|bytes| requests::parse_testcases(&bytes));
+        problem.set_input_testcases(testcases)
+    }
+
+    /// Scrape the list of materials attached to a contest, as `(label, url)`
+    /// pairs.
+    ///
+    /// Same as [`requests::fetch_contest_materials`], but the request is sent
+    /// through this client, so it respects the client's
+    /// [`with_min_interval`](CFClient::with_min_interval).
+    #[cfg(feature = "use_testcase_fetcher")]
+    pub fn fetch_contest_materials(
+        &self,
+        contest_id: i64,
+    ) -> Result<Vec<(String, String)>, Error> {
+        let bytes =
+            self.scrape(&requests::contest_url(&self.web_url, &contest_id))?;
+        Ok(requests::parse_contest_materials(&bytes))
+    }
+
+    /// Scrape the contest page to fill in the fields of `contest` which the
+    /// API left `None`.
+    ///
+    /// Same as [`CFContest::enrich_from_web`](responses::CFContest::enrich_from_web),
+    /// but the request is sent through this client, so it respects the
+    /// client's [`with_min_interval`](CFClient::with_min_interval).
+    #[cfg(feature = "use_testcase_fetcher")]
+    pub fn enrich_contest(
+        &self,
+        contest: &mut responses::CFContest,
+    ) -> Result<(), Error> {
+        let bytes =
+            self.scrape(&requests::contest_url(&self.web_url, &contest.id))?;
+        contest.enrich_from_page(&bytes);
+        Ok(())
+    }
+
+    /// Send a scraping request for a page of the Codeforces website and
+    /// return the raw bytes of the response body.
+    #[cfg(feature = "use_testcase_fetcher")]
+    fn scrape(&self, url: &str) -> Result<Vec<u8>, Error> {
+        Ok(self.send(url)?.bytes()?.to_vec())
+    }
+
+    /// Scrape the submissions pages of `handle` on the Codeforces website to
+    /// get the set of problems they have solved, as `(contest_id, index)`
+    /// pairs.
+    ///
+    /// This is a heuristic over the Codeforces web UI rather than the API: a
+    /// problem counts as solved if any of the user's listed submissions to it
+    /// is accepted. Pages are fetched until one lists no submissions which
+    /// have not already been seen, or `max_pages` pages have been fetched.
+    /// Each page lists only a few dozen submissions, so this needs many more
+    /// requests than deriving the solved set from a single
+    /// [`user.status`](requests::CFUserCommand::Status) call (see
+    /// [`solved_count`](CFClient::solved_count)), and is less reliable since
+    /// the web UI may change. Every page is requested through this client, so
+    /// it respects the client's
+    /// [`with_min_interval`](CFClient::with_min_interval).
+    #[cfg(feature = "use_testcase_fetcher")]
+    pub fn fetch_solved_problems(
+        &self,
+        handle: &str,
+        max_pages: usize,
+    ) -> Result<HashSet<(i64, String)>, Error> {
+        let mut seen = HashSet::new();
+        let mut solved = HashSet::new();
+        for page in 1..=max_pages {
+            let bytes = self.scrape(&requests::submissions_url(
+                &self.web_url,
+                handle,
+                page,
+            )?)?;
+            let (ids, page_solved) = requests::parse_submissions_page(&bytes);
+            // past the last page, Codeforces shows the last page again.
+            let seen_before = seen.len();
+            seen.extend(ids);
+            if seen.len() == seen_before {
+                break;
+            }
+            solved.extend(page_solved);
+        }
+        Ok(solved)
+    }
+
+    /// Scrape the input testcases of every problem in a contest.
+    ///
+    /// The problems of the contest are first fetched through
+    /// `contest.standings`, then each problem page is scraped in turn.
+    /// Returns a `(problem_index, testcases)` pair per problem, where a
+    /// failed scrape is reported as an `Err` for that problem only rather than
+    /// aborting the whole batch.
+    #[cfg(feature = "use_testcase_fetcher")]
+    pub fn fetch_all_testcases(
+        &self,
+        contest_id: i64,
+    ) -> Result<Vec<ProblemTestcases>, Error> {
+        let x = requests::CFContestCommand::Standings {
+            contest_id,
+            from: Some(1),
+            count: Some(1),
+            handles: None,
+            room: None,
+            show_unofficial: None,
+        };
+        let problems = match self.get(&x)? {
+            responses::CFResult::CFContestStandings(s) => s.problems,
+            r => return Err(Error::UnexpectedResponseType(r.variant_name())),
+        };
+        Ok(problems
+            .into_iter()
+            .filter_map(|p| p.index)
+            .map(|index| {
+                let testcases = self
+                    .scrape(&requests::problem_url(
+                        &self.web_url,
+                        &contest_id,
+                        &index,
+                    ))
+                    .and_then(|bytes| requests::parse_testcases(&bytes));
+                (index, testcases)
+            })
+            .collect())
+    }
+
+    /// Poll the standings of a contest every `interval` until the contest
+    /// phase becomes [`Finished`](responses::CFContestPhase::Finished) or
+    /// `stop` is set, calling `on_change` with the rows whose rank or points
+    /// changed since the previous poll.
+    ///
+    /// See [`monitor_standings`] for details.
+    pub fn contest_monitor<C>(
+        &self,
+        contest_id: i64,
+        interval: Duration,
+        stop: &AtomicBool,
+        on_change: C,
+    ) -> Result<(), Error>
+    where
+        C: FnMut(&[&responses::CFRanklistRow]),
+    {
+        let x = requests::CFContestCommand::Standings {
+            contest_id,
+            from: None,
+            count: None,
+            handles: None,
+            room: None,
+            show_unofficial: None,
+        };
+        monitor_standings(
+            || match self.get(&x)? {
+                responses::CFResult::CFContestStandings(s) => Ok(s),
+                r => Err(Error::UnexpectedResponseType(r.variant_name())),
+            },
+            interval,
+            stop,
+            on_change,
+        )
+    }
+
+    /// Poll `recentActions` (fetching the `max_count` most recent actions)
+    /// every `interval` until `stop` is set, calling `on_new` with only the
+    /// actions which were not seen in a previous poll.
+    ///
+    /// See [`poll_recent_actions`] for details.
+    pub fn stream_recent_actions<C>(
+        &self,
+        max_count: i64,
+        interval: Duration,
+        stop: &AtomicBool,
+        on_new: C,
+    ) -> Result<(), Error>
+    where
+        C: FnMut(&[responses::CFRecentAction]),
+    {
+        let x = requests::CFRecentActionsCommand::new(max_count);
+        poll_recent_actions(
+            || match self.get(&x)? {
+                responses::CFResult::CFRecentActionVec(v) => Ok(v),
+                r => Err(Error::UnexpectedResponseType(r.variant_name())),
+            },
+            interval,
+            stop,
+            on_new,
+        )
+    }
+
+    /// Fetch all non-gym contests (`contest.list`) which have not started yet
+    /// and are scheduled to start within the next `within_seconds` seconds.
+    ///
+    /// Contests whose start time is unknown are excluded. See
+    /// [`CFContest::starts_within`](responses::CFContest::starts_within).
+    pub fn upcoming_contests(
+        &self,
+        within_seconds: i64,
+    ) -> Result<Vec<responses::CFContest>, Error> {
+        let x = requests::CFContestCommand::List { gym: Some(false) };
+        match self.get(&x)? {
+            responses::CFResult::CFContestVec(v) => {
+                let now = unix_time();
+                Ok(v.into_iter()
+                    .filter(|c| c.starts_within(now, within_seconds))
+                    .collect())
+            }
+            r => Err(Error::UnexpectedResponseType(r.variant_name())),
+        }
+    }
+
+    /// Fetch only the submissions of `handle` which are newer than the
+    /// submission with id `last_id` (ie. with a greater id).
+    ///
+    /// `user.status` is paged from the most recent submission, stopping as
+    /// soon as a submission with `id <= last_id` is reached, so only the
+    /// pages containing new submissions are fetched. See
+    /// [`submissions_newer_than`].
+    pub fn new_submissions_since(
+        &self,
+        handle: &str,
+        last_id: i64,
+    ) -> Result<Vec<responses::CFSubmission>, Error> {
+        submissions_newer_than(last_id, STATUS_PAGE_SIZE, |from, count| {
+            let x = requests::CFUserCommand::Status {
+                handle: handle.to_string(),
+                from: Some(from),
+                count: Some(count),
+            };
+            match self.get(&x)? {
+                responses::CFResult::CFSubmissionVec(v) => Ok(v),
+                r => Err(Error::UnexpectedResponseType(r.variant_name())),
+            }
+        })
+    }
+
+    /// Fetch the standings (`contest.standings`) of contest `contest_id`
+    /// restricted to the given handles, including unofficial participants
+    /// (eg. virtual or out of competition). Useful for seeing where a group of
+    /// friends placed in a contest.
+    pub fn standings_for_handles(
+        &self,
+        contest_id: i64,
+        handles: &[&str],
+    ) -> Result<responses::CFContestStandings, Error> {
+        let x = requests::CFContestCommand::Standings {
+            contest_id,
+            from: None,
+            count: None,
+            handles: Some(handles.iter().map(|h| h.to_string()).collect()),
+            room: None,
+            show_unofficial: Some(true),
+        };
+        match self.get(&x)? {
+            responses::CFResult::CFContestStandings(s) => Ok(s),
+            r => Err(Error::UnexpectedResponseType(r.variant_name())),
+        }
+    }
+
+    /// Fetch the problemset (`problemset.problems`) and pick a random problem
+    /// which carries all of `tags` and is rated within
+    /// `min_rating..=max_rating` (see
+    /// [`CFProblemset::random_problem`](responses::CFProblemset::random_problem)).
+    /// Passing a `seed` makes the pick reproducible. Returns `None` if no
+    /// problem matches.
+    pub fn random_problem(
+        &self,
+        tags: &[&str],
+        min_rating: i64,
+        max_rating: i64,
+        seed: Option<u64>,
+    ) -> Result<Option<responses::CFProblem>, Error> {
+        let x = requests::CFProblemsetCommand::Problems {
+            tags: if tags.is_empty() {
+                None
+            } else {
+                Some(tags.iter().map(|t| t.to_string()).collect())
+            },
+            problemset_name: None,
+        };
+        match self.get(&x)? {
+            responses::CFResult::CFProblemset(p) => Ok(p
+                .random_problem(tags, min_rating, max_rating, seed)
+                .cloned()),
+            r => Err(Error::UnexpectedResponseType(r.variant_name())),
+        }
+    }
+
+    /// Fetch the blog entries of `handle` (`user.blogEntries`) along with
+    /// their full content.
+    ///
+    /// `user.blogEntries` only returns the metadata of each entry, so the
+    /// content of each entry lacking it is then fetched with `blogEntry.view`,
+    /// one request per entry. Use
+    /// [`with_min_interval`](CFClient::with_min_interval) to stay within the
+    /// API rate limit when a user has many entries.
+    pub fn blog_entries_with_content(
+        &self,
+        handle: &str,
+    ) -> Result<Vec<responses::CFBlogEntry>, Error> {
+        let x = requests::CFUserCommand::BlogEntries {
+            handle: handle.to_string(),
+        };
+        let mut entries = match self.get(&x)? {
+            responses::CFResult::CFBlogEntryVec(v) => v,
+            r => return Err(Error::UnexpectedResponseType(r.variant_name())),
+        };
+        for entry in entries.iter_mut().filter(|e| e.content.is_none()) {
+            let x = requests::CFBlogEntryCommand::View {
+                blog_entry_id: entry.id,
+            };
+            match self.get(&x)? {
+                responses::CFResult::CFBlogEntry(full) => {
+                    entry.content = full.content;
+                }
+                r => {
+                    return Err(Error::UnexpectedResponseType(r.variant_name()))
+                }
+            }
+        }
+        Ok(entries)
+    }
+
+    /// Fetch the distinct set of problem tags used in the problemset (eg.
+    /// `"dp"`, `"greedy"`), sorted alphabetically. Useful for validating
+    /// user-supplied tags.
+    ///
+    /// The tags are derived from `problemset.problems` on the first call and
+    /// cached in the client (shared with its clones) for later calls.
+    pub fn known_tags(&self) -> Result<Vec<String>, Error> {
+        let mut known_tags = self.known_tags.lock().unwrap();
+        if let Some(ref tags) = *known_tags {
+            self.stats.cache_hits.fetch_add(1, Ordering::Relaxed);
+            return Ok(tags.clone());
+        }
+        let x = requests::CFProblemsetCommand::Problems {
+            tags: None,
+            problemset_name: None,
+        };
+        match self.get(&x)? {
+            responses::CFResult::CFProblemset(p) => {
+                let tags = p.distinct_tags();
+                *known_tags = Some(tags.clone());
+                Ok(tags)
+            }
+            r => Err(Error::UnexpectedResponseType(r.variant_name())),
+        }
+    }
+
+    /// Lazily iterate over submissions, newest first, fetching pages of
+    /// submissions only as they are consumed (see [`SubmissionPages`]).
+    ///
+    /// If `contest_id` is given, submissions of that contest are streamed
+    /// (`contest.status`), optionally only those of `handle`. Otherwise, all
+    /// submissions of `handle` are streamed (`user.status`). If neither is
+    /// given, the stream yields a single [`Error::InvalidParameter`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use codeforces_api::client::CFClient;
+    /// # let api_key = codeforces_api::TEST_API_KEY;
+    /// # let api_secret = codeforces_api::TEST_API_SECRET;
+    /// let client = CFClient::new(api_key, api_secret);
+    /// let latest: Vec<_> = client
+    ///     .submissions_page_stream(None, Some("thud"))
+    ///     .take(5)
+    ///     .collect();
+    /// ```
+    pub fn submissions_page_stream<'a>(
+        &'a self,
+        contest_id: Option<i64>,
+        handle: Option<&'a str>,
+    ) -> SubmissionPages<
+        impl FnMut(i64, i64) -> Result<Vec<responses::CFSubmission>, Error> + 'a,
+    > {
+        SubmissionPages::new(STATUS_PAGE_SIZE, move |from, count| {
+            let res = match (contest_id, handle) {
+                (Some(contest_id), handle) => {
+                    self.get(&requests::CFContestCommand::Status {
+                        contest_id,
+                        handle: handle.map(|h| h.to_string()),
+                        from: Some(from),
+                        count: Some(count),
+                    })?
+                }
+                (None, Some(handle)) => {
+                    self.get(&requests::CFUserCommand::Status {
+                        handle: handle.to_string(),
+                        from: Some(from),
+                        count: Some(count),
+                    })?
+                }
+                (None, None) => {
+                    return Err(Error::InvalidParameter(
+                        "contest_id or handle is required",
+                    ))
+                }
+            };
+            match res {
+                responses::CFResult::CFSubmissionVec(v) => Ok(v),
+                r => Err(Error::UnexpectedResponseType(r.variant_name())),
+            }
+        })
+    }
+
+    /// Fetch the current rating of `handle` (`user.info`), or `None` if the
+    /// user is unrated.
+    pub fn rating_of(&self, handle: &str) -> Result<Option<i64>, Error> {
+        Ok(self.user_info(handle)?.and_then(|u| u.rating))
+    }
+
+    /// Fetch the user whose handle was set with
+    /// [`with_handle`](CFClient::with_handle) (`user.info`).
+    ///
+    /// The owner of the API key cannot be determined from the key itself, so
+    /// the handle has to be given explicitly; [`Error::InvalidParameter`] is
+    /// returned if it was not set, or if the response does not include the
+    /// user with that handle. The handle is not checked against the key.
+    pub fn whoami(&self) -> Result<responses::CFUser, Error> {
+        let handle = self.handle.as_deref().ok_or(Error::InvalidParameter(
+            "no handle set, see CFClient::with_handle",
+        ))?;
+        self.user_info(handle)?.ok_or(Error::InvalidParameter(
+            "handle set with CFClient::with_handle was not found",
+        ))
+    }
+
+    /// Fetch the user with the given `handle` (`user.info`), matched
+    /// case-insensitively.
+    fn user_info(
+        &self,
+        handle: &str,
+    ) -> Result<Option<responses::CFUser>, Error> {
+        let x = requests::CFUserCommand::Info {
+            handles: vec![handle.to_string()],
+        };
+        match self.get(&x)? {
+            responses::CFResult::CFUserVec(v) => Ok(v
+                .into_iter()
+                .find(|u| u.handle.eq_ignore_ascii_case(handle))),
+            r => Err(Error::UnexpectedResponseType(r.variant_name())),
+        }
+    }
+
+    /// Fetch all rated users (`user.ratedList`) whose rating lies within
+    /// `min..=max`, eg. to find users of a similar level to invite to a
+    /// mashup. If `active_only` is `true`, only users who took part in a rated
+    /// contest during the last month are considered.
+    pub fn users_in_rating_range(
+        &self,
+        min: i64,
+        max: i64,
+        active_only: bool,
+    ) -> Result<Vec<responses::CFUser>, Error> {
+        let x = requests::CFUserCommand::RatedList {
+            active_only: Some(active_only),
+        };
+        match self.get(&x)? {
+            responses::CFResult::CFUserVec(v) => Ok(v
+                .into_iter()
+                .filter(|u| u.rating.is_some_and(|r| min <= r && r <= max))
+                .collect()),
+            r => Err(Error::UnexpectedResponseType(r.variant_name())),
+        }
+    }
+
+    /// Fetch all contests (`contest.list`) and count them by duration,
+    /// bucketed into whole hours (see
+    /// [`contest_duration_histogram`](responses::contest_duration_histogram)).
+    ///
+    /// `gym` is passed on as is: `Some(true)` for only gym contests, and
+    /// otherwise only regular contests.
+    pub fn contest_duration_histogram(
+        &self,
+        gym: Option<bool>,
+    ) -> Result<BTreeMap<i64, usize>, Error> {
+        let x = requests::CFContestCommand::List { gym };
+        match self.get(&x)? {
+            responses::CFResult::CFContestVec(v) => {
+                Ok(responses::contest_duration_histogram(&v))
+            }
+            r => Err(Error::UnexpectedResponseType(r.variant_name())),
+        }
+    }
+
+    /// Fetch all contests (`contest.list`) and return those whose name
+    /// contains `query`, ignoring case (see
+    /// [`contests_matching_name`](responses::contests_matching_name)).
+    ///
+    /// `gym` is passed on as is: `Some(true)` for only gym contests, and
+    /// otherwise only regular contests.
+    pub fn find_contests_by_name(
+        &self,
+        query: &str,
+        gym: Option<bool>,
+    ) -> Result<Vec<responses::CFContest>, Error> {
+        let x = requests::CFContestCommand::List { gym };
+        match self.get(&x)? {
+            responses::CFResult::CFContestVec(v) => {
+                Ok(responses::contests_matching_name(v, query))
+            }
+            r => Err(Error::UnexpectedResponseType(r.variant_name())),
+        }
+    }
+
+    /// Fetch the submissions of `handle` (`user.status`) and return the time
+    /// of their first accepted submission to problem `index` of contest
+    /// `contest_id`, or `None` if they have not solved it (see
+    /// [`first_accepted_time`](responses::first_accepted_time)).
+    pub fn first_ac_time(
+        &self,
+        handle: &str,
+        contest_id: i64,
+        index: &str,
+    ) -> Result<Option<i64>, Error> {
+        let x = requests::CFUserCommand::Status {
+            handle: handle.to_string(),
+            from: None,
+            count: None,
+        };
+        match self.get(&x)? {
+            responses::CFResult::CFSubmissionVec(v) => {
+                Ok(responses::first_accepted_time(&v, contest_id, index))
+            }
+            r => Err(Error::UnexpectedResponseType(r.variant_name())),
+        }
+    }
+
+    /// Run `f` once per handle in `handles`, in order, collecting each
+    /// outcome. `f` is given this client and the handle, and typically builds
+    /// and sends one request, eg. to fetch the rating of each friend.
+    ///
+    /// Requests are spaced out according to the client's
+    /// [`with_min_interval`](CFClient::with_min_interval), or by 2 seconds
+    /// (the Codeforces API limit) if no minimum interval is set, and a
+    /// failure for one handle does not stop the others from being fetched.
+    pub fn map_handles<F, T>(
+        &self,
+        handles: &[String],
+        mut f: F,
+    ) -> Vec<Result<T, Error>>
+    where
+        F: FnMut(&CFClient, &str) -> Result<T, Error>,
+    {
+        let client = CFClient {
+            min_interval: self.min_interval.or(Some(DEFAULT_MIN_INTERVAL)),
+            ..self.clone()
+        };
+        handles.iter().map(|h| f(&client, h)).collect()
+    }
+
+    /// Fetch the submissions of `handle` (`user.status`) and count the
+    /// distinct problems they have solved (see
+    /// [`solved_problems`](responses::solved_problems)).
+    pub fn solved_count(&self, handle: &str) -> Result<usize, Error> {
+        let x = requests::CFUserCommand::Status {
+            handle: handle.to_string(),
+            from: None,
+            count: None,
+        };
+        match self.get(&x)? {
+            responses::CFResult::CFSubmissionVec(v) => {
+                Ok(responses::solved_problems(&v).len())
+            }
+            r => Err(Error::UnexpectedResponseType(r.variant_name())),
+        }
+    }
+}
+
+/// Builder for a [`CFClient`] with several options configured at once, as
+/// returned by [`CFClient::builder`].
+///
+/// Each setter corresponds to one of the `with_*` methods of [`CFClient`].
+/// All options are validated together by [`build`](CFClientBuilder::build).
+#[derive(Clone, Default)]
+pub struct CFClientBuilder {
+    api_key: Option<String>,
+    api_secret: Option<String>,
+    timeout: Option<Duration>,
+    connect_timeout: Option<Duration>,
+    pool_idle_timeout: Option<Duration>,
+    dry_run: bool,
+    min_interval: Option<Duration>,
+    base_url: Option<String>,
+    web_url: Option<String>,
+    https_only: bool,
+    deadline: Option<Instant>,
+    retries: Option<Retries>,
+    handle: Option<String>,
+    max_response_bytes: Option<usize>,
+    request_logger: Option<RequestLogger>,
+}
+
+impl fmt::Debug for CFClientBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CFClientBuilder")
+            .field("api_key", &self.api_key)
+            .field("timeout", &self.timeout)
+            .field("connect_timeout", &self.connect_timeout)
+            .field("pool_idle_timeout", &self.pool_idle_timeout)
+            .field("dry_run", &self.dry_run)
+            .field("min_interval", &self.min_interval)
+            .field("base_url", &self.base_url)
+            .field("web_url", &self.web_url)
+            .field("https_only", &self.https_only)
+            .field("deadline", &self.deadline)
+            .field("retries", &self.retries.as_ref().map(|r| r.max_retries))
+            .field("handle", &self.handle)
+            .field("max_response_bytes", &self.max_response_bytes)
+            .field("request_logger", &self.request_logger.is_some())
+            .finish_non_exhaustive()
+    }
+}
+
+impl CFClientBuilder {
+    /// Set the API key and secret which every request is signed with.
+    pub fn credentials(mut self, api_key: &str, api_secret: &str) -> Self {
+        self.api_key = Some(api_key.to_string());
+        self.api_secret = Some(api_secret.to_string());
+        self
+    }
+
+    /// See [`CFClient::with_timeout`].
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// See [`CFClient::with_connect_timeout`].
+    pub fn connect_timeout(mut self, connect_timeout: Duration) -> Self {
+        self.connect_timeout = Some(connect_timeout);
+        self
+    }
+
+    /// See [`CFClient::with_pool_idle_timeout`].
+    pub fn pool_idle_timeout(mut self, pool_idle_timeout: Duration) -> Self {
+        self.pool_idle_timeout = Some(pool_idle_timeout);
+        self
+    }
+
+    /// See [`CFClient::with_handle`].
+    pub fn handle(mut self, handle: &str) -> Self {
+        self.handle = Some(handle.to_string());
+        self
+    }
+
+    /// See [`CFClient::with_max_response_bytes`].
+    pub fn max_response_bytes(mut self, max_response_bytes: usize) -> Self {
+        self.max_response_bytes = Some(max_response_bytes);
+        self
+    }
+
+    /// See [`CFClient::with_dry_run`].
+    pub fn dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    /// See [`CFClient::with_min_interval`].
+    pub fn min_interval(mut self, min_interval: Duration) -> Self {
+        self.min_interval = Some(min_interval);
+        self
+    }
+
+    /// See [`CFClient::with_base_url`].
+    pub fn base_url(mut self, api_url: &str) -> Self {
+        self.base_url = Some(api_url.to_string());
+        self
+    }
+
+    /// See [`CFClient::with_web_url`].
+    pub fn web_url(mut self, web_url: &str) -> Self {
+        self.web_url = Some(web_url.to_string());
+        self
+    }
+
+    /// See [`CFClient::with_https_only`].
+    pub fn https_only(mut self, https_only: bool) -> Self {
+        self.https_only = https_only;
+        self
+    }
+
+    /// See [`CFClient::with_deadline`].
+    pub fn deadline(mut self, deadline: Instant) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+
+    /// See [`CFClient::with_retries`].
+    pub fn retries(mut self, max_retries: u32, base_delay: Duration) -> Self {
+        let on_retry = self.retries.take().and_then(|r| r.on_retry);
+        self.retries = Some(Retries {
+            max_retries,
+            base_delay,
+            on_retry,
+        });
+        self
+    }
+
+    /// See [`CFClient::with_on_retry`].
+    pub fn on_retry<F>(mut self, on_retry: F) -> Self
+    where
+        F: Fn(u32, &Error, Duration) + Send + Sync + 'static,
+    {
+        let retries = self.retries.get_or_insert(Retries {
+            max_retries: 0,
+            base_delay: Duration::from_secs(0),
+            on_retry: None,
+        });
+        retries.on_retry = Some(Arc::new(on_retry));
+        self
+    }
+
+    /// See [`CFClient::with_request_logger`].
+    pub fn request_logger<F>(mut self, logger: F, redact_api_sig: bool) -> Self
+    where
+        F: Fn(&str) + Send + Sync + 'static,
+    {
+        self.request_logger = Some(RequestLogger {
+            callback: Arc::new(logger),
+            redact_api_sig,
+        });
+        self
+    }
+
+    /// Build the configured [`CFClient`].
+    ///
+    /// Returns [`Error::InvalidParameter`] if no (or empty) credentials were
+    /// given, or if HTTPS-only mode is enabled with a base or web url which is
+    /// not an `https://` url. Returns [`Error::Http`] if the underlying network
+    /// client cannot be built (eg. the TLS backend fails to initialize).
+    pub fn build(self) -> Result<CFClient, Error> {
+        let (api_key, api_secret) = match (self.api_key, self.api_secret) {
+            (Some(k), Some(s)) if !k.is_empty() && !s.is_empty() => (k, s),
+            _ => {
+                return Err(Error::InvalidParameter(
+                    "api key and secret are required",
+                ))
+            }
+        };
+        let http = CFClient::http_client(
+            self.https_only,
+            self.timeout,
+            self.connect_timeout,
+            self.pool_idle_timeout,
+        )?;
+        let mut client = CFClient::from_client(http, &api_key, &api_secret);
+        client.timeout = self.timeout;
+        client.connect_timeout = self.connect_timeout;
+        client.pool_idle_timeout = self.pool_idle_timeout;
+        client.dry_run = self.dry_run;
+        client.min_interval = self.min_interval;
+        client.https_only = self.https_only;
+        client.deadline = self.deadline;
+        client.retries = self.retries;
+        client.handle = self.handle;
+        client.max_response_bytes = self.max_response_bytes;
+        client.request_logger = self.request_logger;
+        if let Some(ref api_url) = self.base_url {
+            client = client.with_base_url(api_url)?;
+        }
+        if let Some(ref web_url) = self.web_url {
+            client = client.with_web_url(web_url)?;
+        }
+        client.check_https_only()?;
+        Ok(client)
+    }
+}
+
+impl responses::CFContestStandings {
+    /// Fetches the problemset (`problemset.problems`) once using `client` and
+    /// fills in the `rating` of each of the standings' `problems` which lacks
+    /// one (see
+    /// [`fill_problem_ratings`](responses::CFContestStandings::fill_problem_ratings)).
+    pub fn enrich_problem_ratings(
+        &mut self,
+        client: &CFClient,
+    ) -> Result<(), Error> {
+        let x = requests::CFProblemsetCommand::Problems {
+            tags: None,
+            problemset_name: None,
+        };
+        match client.get(&x)? {
+            responses::CFResult::CFProblemset(p) => {
+                self.fill_problem_ratings(&p);
+                Ok(())
+            }
+            r => Err(Error::UnexpectedResponseType(r.variant_name())),
+        }
+    }
+}
+
+/// Testcases scraped for a single problem by
+/// [`CFClient::fetch_all_testcases`], as a `(problem_index, testcases)` pair.
+#[cfg(feature = "use_testcase_fetcher")]
+pub type ProblemTestcases = (String, Result<Vec<String>, Error>);
+
+/// Number of submissions requested per page by paginating helpers.
+const STATUS_PAGE_SIZE: i64 = 100;
+
+/// Spacing between requests used by batch helpers when the client has no
+/// minimum interval set, matching the Codeforces API limit.
+const DEFAULT_MIN_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Collects submissions with an id greater than `last_id` from a paginated,
+/// newest-first source of submissions.
+///
+/// `fetch_page(from, count)` should return up to `count` submissions starting
+/// at the 1-based index `from` (as with the `from`/`count` parameters of
+/// `user.status`). Paging stops at the first submission with
+/// `id <= last_id`, or when a page is shorter than `page_size`.
+pub fn submissions_newer_than<F>(
+    last_id: i64,
+    page_size: i64,
+    mut fetch_page: F,
+) -> Result<Vec<responses::CFSubmission>, Error>
+where
+    F: FnMut(i64, i64) -> Result<Vec<responses::CFSubmission>, Error>,
+{
+    let mut res = vec![];
+    let mut from = 1;
+    loop {
+        let page = fetch_page(from, page_size)?;
+        let page_len = page.len() as i64;
+        for submission in page {
+            if submission.id <= last_id {
+                return Ok(res);
+            }
+            res.push(submission);
+        }
+        if page_len < page_size {
+            return Ok(res);
+        }
+        from += page_size;
+    }
+}
+
+/// Lazy iterator over a paginated, newest-first source of submissions, as
+/// returned by [`CFClient::submissions_page_stream`].
+///
+/// Pages are only fetched once all submissions of the previous page have been
+/// consumed, so eg. `.take(50)` fetches only as many pages as needed. If
+/// fetching a page fails, the error is yielded as an `Err` item and the
+/// iterator then ends.
+pub struct SubmissionPages<F> {
+    fetch_page: F,
+    page_size: i64,
+    from: i64,
+    buffer: std::vec::IntoIter<responses::CFSubmission>,
+    done: bool,
+}
+
+impl<F> SubmissionPages<F>
+where
+    F: FnMut(i64, i64) -> Result<Vec<responses::CFSubmission>, Error>,
+{
+    /// Create a stream of submissions fetched `page_size` at a time using
+    /// `fetch_page(from, count)`, which should behave as described for
+    /// [`submissions_newer_than`].
+    pub fn new(page_size: i64, fetch_page: F) -> Self {
+        SubmissionPages {
+            fetch_page,
+            page_size,
+            from: 1,
+            buffer: Vec::new().into_iter(),
+            done: false,
+        }
+    }
+}
+
+impl<F> Iterator for SubmissionPages<F>
+where
+    F: FnMut(i64, i64) -> Result<Vec<responses::CFSubmission>, Error>,
+{
+    type Item = Result<responses::CFSubmission, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(submission) = self.buffer.next() {
+            return Some(Ok(submission));
+        }
+        if self.done {
+            return None;
+        }
+        match (self.fetch_page)(self.from, self.page_size) {
+            Ok(page) => {
+                if (page.len() as i64) < self.page_size {
+                    self.done = true;
+                }
+                self.from += self.page_size;
+                self.buffer = page.into_iter();
+                self.buffer.next().map(Ok)
+            }
+            Err(e) => {
+                self.done = true;
+                Some(Err(e))
+            }
+        }
+    }
+}
+
+/// Repeatedly fetches standings using `fetch`, sleeping `interval` between
+/// polls, and calls `on_change` with the rows which changed since the
+/// previous poll (see
+/// [`CFContestStandings::changed_rows`](responses::CFContestStandings::changed_rows)).
+///
+/// The first poll only establishes a baseline, so `on_change` is not called
+/// for it. Polling stops once the fetched contest is
+/// [`Finished`](responses::CFContestPhase::Finished), once `stop` is set, or
+/// as soon as `fetch` returns an error.
+pub fn monitor_standings<F, C>(
+    mut fetch: F,
+    interval: Duration,
+    stop: &AtomicBool,
+    mut on_change: C,
+) -> Result<(), Error>
+where
+    F: FnMut() -> Result<responses::CFContestStandings, Error>,
+    C: FnMut(&[&responses::CFRanklistRow]),
+{
+    let mut previous: Option<responses::CFContestStandings> = None;
+    while !stop.load(Ordering::Relaxed) {
+        let current = fetch()?;
+        if let Some(ref p) = previous {
+            let changed = current.changed_rows(p);
+            if !changed.is_empty() {
+                on_change(&changed);
+            }
+        }
+        if current.contest.phase == responses::CFContestPhase::Finished {
+            break;
+        }
+        previous = Some(current);
+        thread::sleep(interval);
+    }
+    Ok(())
+}
+
+/// Repeatedly fetches recent actions using `fetch`, sleeping `interval`
+/// between polls, and calls `on_new` with the actions (oldest first) which are
+/// newer than any seen before.
+///
+/// The newest `time_seconds` seen so far is tracked, along with which actions
+/// had that time, so overlapping batches report each action exactly once.
+/// Unlike [`monitor_standings`], the first poll is reported too. Polling
+/// stops once `stop` is set, or as soon as `fetch` returns an error.
+pub fn poll_recent_actions<F, C>(
+    mut fetch: F,
+    interval: Duration,
+    stop: &AtomicBool,
+    mut on_new: C,
+) -> Result<(), Error>
+where
+    F: FnMut() -> Result<Vec<responses::CFRecentAction>, Error>,
+    C: FnMut(&[responses::CFRecentAction]),
+{
+    let key = |a: &responses::CFRecentAction| {
+        (
+            a.blog_entry.as_ref().map(|b| b.id),
+            a.comment.as_ref().map(|c| c.id),
+        )
+    };
+    let mut newest = i64::MIN;
+    let mut seen_at_newest = Vec::new();
+    while !stop.load(Ordering::Relaxed) {
+        let mut actions: Vec<_> = fetch()?
+            .into_iter()
+            .filter(|a| {
+                a.time_seconds > newest
+                    || (a.time_seconds == newest
+                        && !seen_at_newest.contains(&key(a)))
+            })
+            .collect();
+        actions.sort_by_key(|a| a.time_seconds);
+        if let Some(last) = actions.last() {
+            if last.time_seconds > newest {
+                newest = last.time_seconds;
+                seen_at_newest.clear();
+            }
+            seen_at_newest.extend(
+                actions.iter().filter(|a| a.time_seconds == newest).map(key),
+            );
+            on_new(&actions);
+        }
+        thread::sleep(interval);
+    }
+    Ok(())
+}
+
+/// Current UNIX time in seconds.
+fn unix_time() -> i64 {
+    SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap()
+        .as_secs() as i64
+}
+
+/// Replaces the value of the `apiSig` query parameter in `url` (if any) with
+/// `<redacted>`.
+fn redact_api_sig(url: &str) -> String {
+    match url.find("apiSig=") {
+        Some(i) => url[..i].to_string() + "apiSig=<redacted>",
+        None => url.to_string(),
+    }
+}
@@ -2,7 +2,11 @@
 ///
 /// When sending a request is unsuccessful, a variant of the [`Error`] type
 /// will be returned.
+///
+/// New variants may be added in future releases, so matches on this type
+/// need a wildcard arm.
 #[derive(Debug)]
+#[non_exhaustive]
 pub enum Error {
     /// `Http` errors are a wrapper for network errors returned internally by
     /// reqwest.
@@ -14,8 +18,9 @@ pub enum Error {
     /// `Parse` errors are a wrapper for parsing errors returned internally by
     /// reqwest.
     ///
-    /// This could be returned if, for example, the Codeforces API returns
-    /// malformed JSON. Further documentation can be found with the
+    /// This is no longer returned by this crate: every response body is
+    /// parsed with serde_json, so malformed JSON is always reported as
+    /// [`Error::Json`]. Further documentation can be found with the
     /// [`reqwest::Error`] type.
     Parse(reqwest::Error),
     /// `CodeforcesApi` errors are returned when the Codeforces API returns a
@@ -28,6 +33,146 @@ pub enum Error {
     /// For now, a simple message (`&'static str`) is returned, outlining the
     /// error. However, in future, this could/should be moved into its own enum.
     Testcases(&'static str),
+    /// `Io` errors are a wrapper for filesystem errors, returned when writing
+    /// fetched testcases to disk fails.
+    Io(std::io::Error),
+    /// `UnexpectedResponseType` errors are returned by [`CFClient`] helper
+    /// methods when the Codeforces API returns a different kind of
+    /// [`CFResult`] than the one expected for the request sent. The name of
+    /// the variant which was received is returned as a `&'static str` (see
+    /// [`CFResult::variant_name`]).
+    ///
+    /// [`CFClient`]: crate::client::CFClient
+    /// [`CFResult`]: crate::responses::CFResult
+    /// [`CFResult::variant_name`]: crate::responses::CFResult::variant_name
+    UnexpectedResponseType(&'static str),
+    /// `DryRun` errors are returned instead of sending any request when a
+    /// client is in dry-run mode (see
+    /// [`CFClient::with_dry_run`](crate::client::CFClient::with_dry_run)).
+    /// The fully signed url which would have been requested is returned as a
+    /// [`String`].
+    DryRun(String),
+    /// `Json` errors are a wrapper for parsing errors returned internally by
+    /// serde_json when parsing a fetched response body.
+    ///
+    /// This is returned by every request method if the Codeforces API
+    /// returns malformed JSON. Further documentation can be found with the
+    /// [`serde_json::Error`] type.
+    Json(serde_json::Error),
+    /// `InvalidParameter` errors are returned when a request or client option
+    /// is known to be invalid before anything is sent to Codeforces.
+    ///
+    /// A simple message (`&'static str`) is returned, outlining which
+    /// parameter is invalid.
+    InvalidParameter(&'static str),
+    /// `ServiceUnavailable` errors are returned when Codeforces responds with
+    /// an HTML page instead of JSON, which happens during maintenance or under
+    /// heavy load. Such requests are worth retrying later.
+    ServiceUnavailable,
+    /// `DeadlineExceeded` errors are returned instead of sending a request
+    /// once the client's overall deadline has passed (see
+    /// [`CFClient::with_deadline`](crate::client::CFClient::with_deadline)).
+    /// Batch helpers return these for the requests they did not get to, so
+    /// any results fetched before the deadline are kept.
+    DeadlineExceeded,
+    /// `ResponseTooLarge` errors are returned when a response body exceeds
+    /// the limit set with
+    /// [`CFClient::with_max_response_bytes`](crate::client::CFClient::with_max_response_bytes).
+    /// Reading is aborted as soon as the limit is exceeded. The limit (in
+    /// bytes) is returned as a [`usize`].
+    ResponseTooLarge(usize),
+    /// `MissingResult` errors are returned when the Codeforces API responds
+    /// with `status: OK` but without a `result` field, which should not
+    /// happen for well-formed responses.
+    MissingResult,
+}
+
+impl Error {
+    /// Returns `true` if the request which caused this error is worth
+    /// retrying later.
+    ///
+    /// Transient network errors (timeouts, failed connections, `429` and
+    /// `5xx` status codes) and Codeforces API rate-limit failures
+    /// (`Call limit exceeded`) are retryable, as are
+    /// [`Error::ServiceUnavailable`] errors. Everything else, such as
+    /// authentication failures, missing resources and parse errors, is not.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Error::Http(ref e) => {
+                e.is_timeout()
+                    || e.is_connect()
+                    || e.status().is_some_and(|s| {
+                        s.is_server_error()
+                            || s == reqwest::StatusCode::TOO_MANY_REQUESTS
+                    })
+            }
+            Error::CodeforcesApi(_) => {
+                self.failure_kind() == Some(CFFailureKind::CallLimitExceeded)
+            }
+            Error::ServiceUnavailable => true,
+            Error::Parse(_)
+            | Error::Testcases(_)
+            | Error::Io(_)
+            | Error::UnexpectedResponseType(_)
+            | Error::DryRun(_)
+            | Error::Json(_)
+            | Error::InvalidParameter(_)
+            | Error::DeadlineExceeded
+            | Error::ResponseTooLarge(_)
+            | Error::MissingResult => false,
+        }
+    }
+
+    /// Classifies a `CodeforcesApi` error by the comment returned by
+    /// Codeforces, so that expected failures can be told apart from real
+    /// errors. Returns `None` for all other variants.
+    pub fn failure_kind(&self) -> Option<CFFailureKind> {
+        match self {
+            Error::CodeforcesApi(ref s) => Some(CFFailureKind::from_comment(s)),
+            _ => None,
+        }
+    }
+
+    /// Returns the underlying [`reqwest::Error`] of `Http` and `Parse`
+    /// errors, to inspect reqwest-specific state (eg. `is_timeout()`,
+    /// `is_connect()`). Returns `None` for all other variants.
+    pub fn as_reqwest(&self) -> Option<&reqwest::Error> {
+        match self {
+            Error::Http(ref e) | Error::Parse(ref e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+/// Reason for a `status: FAILED` response from the Codeforces API, as
+/// returned by [`Error::failure_kind`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CFFailureKind {
+    /// Rating changes are not available for the contest, eg. because it is
+    /// unrated, a gym contest, or its rating changes are not final yet.
+    RatingChangesUnavailable,
+    /// Too many requests were sent; the request is worth retrying later.
+    CallLimitExceeded,
+    /// A requested resource (eg. a handle or contest) does not exist.
+    NotFound,
+    /// Any other failure.
+    Other,
+}
+
+impl CFFailureKind {
+    /// Classifies the `comment` of a failed response.
+    pub fn from_comment(comment: &str) -> CFFailureKind {
+        let comment = comment.to_lowercase();
+        if comment.contains("rating changes are unavailable") {
+            CFFailureKind::RatingChangesUnavailable
+        } else if comment.contains("call limit exceeded") {
+            CFFailureKind::CallLimitExceeded
+        } else if comment.contains("not found") {
+            CFFailureKind::NotFound
+        } else {
+            CFFailureKind::Other
+        }
+    }
 }
 
 /// Converting from a [`reqwest::Error`] is useful for quickly returning errors
@@ -38,6 +183,14 @@ impl From<reqwest::Error> for Error {
     }
 }
 
+/// Converting from a [`std::io::Error`] is useful for quickly returning
+/// errors internally.
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::Io(e)
+    }
+}
+
 /// Display the error with a short description of the error type as a prefix.
 impl std::fmt::Display for Error {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -46,6 +199,25 @@ impl std::fmt::Display for Error {
             Error::Parse(ref e) => write!(f, "Parse: {}", e),
             Error::CodeforcesApi(ref s) => write!(f, "Codeforces API: {}", s),
             Error::Testcases(ref s) => write!(f, "User: {}", s),
+            Error::Io(ref e) => write!(f, "IO: {}", e),
+            Error::UnexpectedResponseType(ref s) => {
+                write!(f, "Codeforces API: unexpected response type {}", s)
+            }
+            Error::DryRun(ref s) => write!(f, "Dry run: {}", s),
+            Error::Json(ref e) => write!(f, "Parse: {}", e),
+            Error::InvalidParameter(ref s) => {
+                write!(f, "Invalid parameter: {}", s)
+            }
+            Error::ServiceUnavailable => {
+                write!(f, "Codeforces API: service temporarily unavailable")
+            }
+            Error::DeadlineExceeded => write!(f, "Deadline exceeded"),
+            Error::ResponseTooLarge(n) => {
+                write!(f, "Response larger than {} bytes", n)
+            }
+            Error::MissingResult => {
+                write!(f, "Codeforces API: OK response without a result")
+            }
         }
     }
 }
@@ -58,6 +230,15 @@ impl std::error::Error for Error {
             Error::Parse(ref e) => Some(e),
             Error::CodeforcesApi(_) => None,
             Error::Testcases(_) => None,
+            Error::Io(ref e) => Some(e),
+            Error::UnexpectedResponseType(_) => None,
+            Error::DryRun(_) => None,
+            Error::Json(ref e) => Some(e),
+            Error::InvalidParameter(_) => None,
+            Error::ServiceUnavailable => None,
+            Error::DeadlineExceeded => None,
+            Error::ResponseTooLarge(_) => None,
+            Error::MissingResult => None,
         }
     }
 }
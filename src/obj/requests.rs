@@ -2,6 +2,7 @@
 //! and the testcases scraper.
 
 use rand::{self, Rng};
+use serde::de::DeserializeOwned;
 use sha2::{Digest, Sha512};
 use std::time::SystemTime;
 
@@ -12,12 +13,19 @@ use regex::Regex;
 #[cfg(feature = "use_testcase_fetcher")]
 use select::document::Document;
 #[cfg(feature = "use_testcase_fetcher")]
-use select::predicate::{Class, Descendant, Name};
+use select::predicate::{Attr, Class, Descendant, Name};
+#[cfg(feature = "use_testcase_fetcher")]
+use std::collections::HashSet;
+#[cfg(feature = "use_testcase_fetcher")]
+use std::fs;
+#[cfg(feature = "use_testcase_fetcher")]
+use std::path::{Path, PathBuf};
 
 use super::error::*;
 use super::responses;
 
-const API_STUB: &str = "https://codeforces.com/api/";
+pub(crate) const API_STUB: &str = "https://codeforces.com/api/";
+pub(crate) const WEB_STUB: &str = "https://codeforces.com/";
 
 /// Wrapper enum for all API methods of form `blogEntry.<method>`.
 ///
@@ -504,8 +512,8 @@ pub enum CFUserCommand {
     /// }
     /// ```
     Info {
-        /// Vec of handles for which to get info for. Codeforces will return an
-        /// error if this is empty.
+        /// Vec of handles for which to get info for. Must not be empty, or
+        /// [`Error::InvalidParameter`] is returned without sending a request.
         handles: Vec<String>,
     },
     /// Struct for sending `user.ratedList` requests to the Codeforces API.
@@ -656,24 +664,118 @@ pub struct CFRecentActionsCommand {
     pub max_count: i64,
 }
 
+/// Number of recent actions requested by
+/// [`CFRecentActionsCommand::default`].
+pub const DEFAULT_RECENT_ACTIONS_COUNT: i64 = 10;
+
+impl CFRecentActionsCommand {
+    /// Construct a command returning the `max_count` most recent actions.
+    pub fn new(max_count: i64) -> CFRecentActionsCommand {
+        CFRecentActionsCommand { max_count }
+    }
+}
+
+impl Default for CFRecentActionsCommand {
+    /// Requests the [`DEFAULT_RECENT_ACTIONS_COUNT`] most recent actions.
+    fn default() -> Self {
+        CFRecentActionsCommand::new(DEFAULT_RECENT_ACTIONS_COUNT)
+    }
+}
+
 /// Converts CFAPIRequestable object into a Codeforces API url. Currently, only
 /// authenticated interaction is implemented, though in the future, this could
 /// be extended to not require it (ie. no API keys required).
-fn as_codeforces_api_url<T: CFAPIRequestable + std::fmt::Debug>(
+pub(crate) fn as_codeforces_api_url<T: CFAPIRequestable + ?Sized>(
     command: &T,
     api_key: &str,
     api_secret: &str,
 ) -> String {
-    // generate random number to be used as nonce in url.
-    let mut rng = rand::thread_rng();
-    let rand: String = (0..6)
-        .map(|_| rng.gen_range::<u8, _>(0..=9).to_string())
-        .collect();
+    as_codeforces_api_url_with_stub(API_STUB, command, api_key, api_secret)
+}
+
+/// Analogous to `as_codeforces_api_url()`, but with a custom base url for the
+/// API in place of `API_STUB`.
+pub(crate) fn as_codeforces_api_url_with_stub<T: CFAPIRequestable + ?Sized>(
+    api_stub: &str,
+    command: &T,
+    api_key: &str,
+    api_secret: &str,
+) -> String {
+    let rand = generate_nonce();
     // get current UNIX time to be used in url.
     let ctime = SystemTime::now()
         .duration_since(SystemTime::UNIX_EPOCH)
         .unwrap()
         .as_secs();
+    signed_api_url(api_stub, command, api_key, api_secret, &rand, ctime)
+}
+
+/// Generates the random nonce (`rand`) used to sign a request: always
+/// exactly six decimal digits, each drawn independently, so it may have
+/// leading zeros (eg. `"000123"`). It is used as a string only, prefixing the
+/// `apiSig`, and must never be parsed as an integer.
+pub(crate) fn generate_nonce() -> String {
+    let mut rng = rand::thread_rng();
+    (0..6)
+        .map(|_| char::from(b'0' + rng.gen_range::<u8, _>(0..=9)))
+        .collect()
+}
+
+/// Returns the full signed Codeforces API url for `command`, without sending
+/// anything. Useful for passing requests to another HTTP client (eg. an async
+/// framework or `curl`).
+///
+/// ```
+/// # use codeforces_api::requests::*;
+/// # let api_key = codeforces_api::TEST_API_KEY;
+/// # let api_secret = codeforces_api::TEST_API_SECRET;
+/// let x = CFUserCommand::Info {
+///     handles: vec!["thud".to_string()],
+/// };
+/// let url = sign_request(&x, api_key, api_secret);
+/// assert!(url.starts_with("https://codeforces.com/api/user.info?"));
+/// ```
+pub fn sign_request(
+    command: &dyn CFAPIRequestable,
+    api_key: &str,
+    api_secret: &str,
+) -> String {
+    as_codeforces_api_url(command, api_key, api_secret)
+}
+
+/// Analogous to [`sign_request`], but with a caller-chosen nonce and UNIX
+/// time (in seconds) in place of random/current ones, making the resulting
+/// url deterministic. Codeforces expects `nonce` to be six digits.
+pub fn sign_request_with_nonce(
+    command: &dyn CFAPIRequestable,
+    api_key: &str,
+    api_secret: &str,
+    nonce: &str,
+    time: u64,
+) -> String {
+    signed_api_url(API_STUB, command, api_key, api_secret, nonce, time)
+}
+
+/// Order in which query params must appear in a signed API url: sorted
+/// lexicographically (byte-wise) by key, then by value for params which share
+/// a key. Codeforces validates `apiSig` against the params in this order.
+pub(crate) fn cmp_query_params(
+    a: &(&'static str, String),
+    b: &(&'static str, String),
+) -> std::cmp::Ordering {
+    a.0.cmp(b.0).then_with(|| a.1.cmp(&b.1))
+}
+
+/// Constructs and signs an API url from the given nonce (`rand`) and UNIX
+/// time (`ctime`).
+fn signed_api_url<T: CFAPIRequestable + ?Sized>(
+    api_stub: &str,
+    command: &T,
+    api_key: &str,
+    api_secret: &str,
+    rand: &str,
+    ctime: u64,
+) -> String {
     // get command specific query params from method.
     let mut params = command.query_params();
     // add non-specific query params.
@@ -681,20 +783,20 @@ fn as_codeforces_api_url<T: CFAPIRequestable + std::fmt::Debug>(
     params.push(("time", ctime.to_string()));
     // Codeforces requires that the query params be sorted in lexicographical
     // order.
-    params.sort();
-    // construct url by concatenating query params to API_STUB.
-    let mut url = String::from(API_STUB);
+    params.sort_by(cmp_query_params);
+    // construct url by concatenating query params to the API stub.
+    let mut url = String::from(api_stub);
     url += command.method_name();
     url += "?";
     // construct secondary String which will be hashed for checksum.
     let mut to_hash = String::new();
-    to_hash += &rand;
+    to_hash += rand;
     to_hash += "/";
     to_hash += command.method_name();
     to_hash += "?";
     for (key, val) in params {
-        url += &key;
-        to_hash += &key;
+        url += key;
+        to_hash += key;
         url += "=";
         to_hash += "=";
         url += &val;
@@ -704,14 +806,14 @@ fn as_codeforces_api_url<T: CFAPIRequestable + std::fmt::Debug>(
     }
     to_hash.pop();
     to_hash += "#";
-    to_hash += &api_secret.to_string();
+    to_hash += api_secret;
     // hash to_hash then add to end of the url.
     let mut hasher = Sha512::new();
     hasher.update(&to_hash);
     let api_sig = hasher.finalize();
     url += "apiSig=";
-    url += &rand;
-    url += &hex::encode(&api_sig);
+    url += rand;
+    url += &hex::encode(api_sig);
     url
 }
 
@@ -722,26 +824,121 @@ fn send_codeforces_api_req<T: CFAPIRequestable + std::fmt::Debug>(
     api_key: &str,
     api_secret: &str,
 ) -> Result<responses::CFResult, Error> {
+    req.validate()?;
+    #[cfg(feature = "tracing")]
+    let span = request_span(req.method_name());
+    #[cfg(feature = "tracing")]
+    let _enter = span.enter();
     // convert request object into a url String.
     let url = as_codeforces_api_url(req, api_key, api_secret);
-    match get_url(&url) {
-        // if fetch was successful, then parse the JSON into a `CFResponse`.
-        Ok(res) => match res.json::<responses::CFResponse>() {
-            // if parse was successful, then check Codeforces response code.
-            Ok(json) => match json.status {
-                // if response is `Ok`, then return `CFResult` object.
-                responses::CFResponseStatus::Ok => Ok(json.result.unwrap()),
-                // if response is `Failed`, then return `Error::CodeforcesApi`,
-                // with the returned comment as its String param.
-                responses::CFResponseStatus::Failed => {
-                    Err(Error::CodeforcesApi(json.comment.unwrap()))
-                }
-            },
-            // if parse failed, then wrap reqwest parsing error with custom.
-            Err(e) => Err(Error::Parse(e)),
-        },
+    let res = match get_url(&url) {
+        // if fetch was successful, then parse the JSON into a `CFResult`.
+        Ok(res) => parse_codeforces_api_res(res),
         // if fetch failed, then wrap reqwest error with custom Http.
         Err(e) => Err(Error::Http(e)),
+    };
+    #[cfg(feature = "tracing")]
+    record_outcome(&span, &res);
+    res
+}
+
+/// Creates a `tracing` span covering a single API request to `method`
+/// (requires `tracing` feature). The `outcome` field is recorded by
+/// `record_outcome()` once the request has completed.
+#[cfg(feature = "tracing")]
+pub(crate) fn request_span(method: &'static str) -> tracing::Span {
+    tracing::info_span!(
+        "codeforces_api_request",
+        method = method,
+        outcome = tracing::field::Empty,
+    )
+}
+
+/// Records whether the request covered by `span` succeeded or failed
+/// (requires `tracing` feature).
+#[cfg(feature = "tracing")]
+pub(crate) fn record_outcome<T>(span: &tracing::Span, res: &Result<T, Error>) {
+    span.record("outcome", if res.is_ok() { "success" } else { "failure" });
+}
+
+/// Parses a network response from the Codeforces API into a `CFResult`,
+/// mapping `status: FAILED` responses onto `Error::CodeforcesApi`.
+///
+/// The body is read as text and parsed by `parse_codeforces_api_body()`, so
+/// HTML error pages are detected by their content whatever their
+/// Content-Type.
+pub(crate) fn parse_codeforces_api_res(
+    res: reqwest::blocking::Response,
+) -> Result<responses::CFResult, Error> {
+    parse_codeforces_api_body(&res.text()?)
+}
+
+/// Analogous to `parse_codeforces_api_res()`, but parses an already fetched
+/// JSON String.
+pub(crate) fn parse_codeforces_api_body(
+    body: &str,
+) -> Result<responses::CFResult, Error> {
+    parse_codeforces_api_body_with_comment(body).map(|(result, _)| result)
+}
+
+/// Analogous to `parse_codeforces_api_body()`, but also returns the
+/// `comment` of successful responses, which Codeforces occasionally sets
+/// (eg. for deprecation notices). Empty comments are returned as `None`.
+pub(crate) fn parse_codeforces_api_body_with_comment(
+    body: &str,
+) -> Result<(responses::CFResult, Option<String>), Error> {
+    check_not_html(body)?;
+    match serde_json::from_str::<responses::CFResponse>(body) {
+        Ok(json) => match json.status {
+            responses::CFResponseStatus::Ok => Ok((
+                json.result.ok_or(Error::MissingResult)?,
+                json.comment.filter(|c| !c.is_empty()),
+            )),
+            responses::CFResponseStatus::Failed => {
+                Err(Error::CodeforcesApi(json.comment.unwrap_or_default()))
+            }
+        },
+        Err(e) => Err(Error::Json(e)),
+    }
+}
+
+/// Returns `Error::ServiceUnavailable` if a fetched body is an HTML page
+/// rather than JSON, as served by Codeforces (with a success status code and
+/// not always a `text/html` Content-Type) when it is temporarily
+/// unavailable.
+fn check_not_html(body: &str) -> Result<(), Error> {
+    if body.trim_start().starts_with('<') {
+        return Err(Error::ServiceUnavailable);
+    }
+    Ok(())
+}
+
+/// Response wrapper analogous to [`responses::CFResponse`], but with a
+/// caller-chosen type for `result`.
+#[derive(serde::Deserialize)]
+struct CFResponseInto<T> {
+    status: responses::CFResponseStatus,
+    result: Option<T>,
+    comment: Option<String>,
+}
+
+/// Analogous to `parse_codeforces_api_body()`, but deserializes the `result`
+/// field of the response into any type `T` instead of a `CFResult`.
+pub(crate) fn parse_codeforces_api_body_into<T: DeserializeOwned>(
+    body: &str,
+) -> Result<T, Error> {
+    check_not_html(body)?;
+    match serde_json::from_str::<CFResponseInto<T>>(body) {
+        Ok(json) => match (json.status, json.result) {
+            (responses::CFResponseStatus::Ok, Some(result)) => Ok(result),
+            (responses::CFResponseStatus::Ok, None) => {
+                Err(Error::MissingResult)
+            }
+            (responses::CFResponseStatus::Failed, _) => {
+                Err(Error::CodeforcesApi(json.comment.unwrap_or_default()))
+            }
+        },
+        Err(e) => Err(Error::Json(e)),
     }
 }
 
@@ -752,6 +949,7 @@ fn send_codeforces_api_req_raw<T: CFAPIRequestable + std::fmt::Debug>(
     api_key: &str,
     api_secret: &str,
 ) -> Result<String, Error> {
+    req.validate()?;
     let url = as_codeforces_api_url(req, api_key, api_secret);
     get_url_raw(&url)
 }
@@ -775,6 +973,82 @@ fn get_url_raw(url: &str) -> Result<String, Error> {
     }
 }
 
+/// Analogous to `get_url()`, but immediately returns the raw bytes of the
+/// response body without assuming any text encoding. Used by the scrapers.
+#[cfg(feature = "use_testcase_fetcher")]
+fn get_url_bytes(url: &str) -> Result<Vec<u8>, Error> {
+    match get_url(url) {
+        Ok(res) => match res.bytes() {
+            Ok(bytes) => Ok(bytes.to_vec()),
+            Err(e) => Err(Error::Http(e)),
+        },
+        Err(e) => Err(Error::Http(e)),
+    }
+}
+
+/// Codeforces API method which a request is sent to, as returned by
+/// [`CFAPIRequestable::method`]. Safer than comparing method name strings
+/// when matching or routing on requests.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CFMethod {
+    /// `blogEntry.comments`
+    BlogEntryComments,
+    /// `blogEntry.view`
+    BlogEntryView,
+    /// `contest.hacks`
+    ContestHacks,
+    /// `contest.list`
+    ContestList,
+    /// `contest.ratingChanges`
+    ContestRatingChanges,
+    /// `contest.standings`
+    ContestStandings,
+    /// `contest.status`
+    ContestStatus,
+    /// `problemset.problems`
+    ProblemsetProblems,
+    /// `problemset.recentStatus`
+    ProblemsetRecentStatus,
+    /// `recentActions`
+    RecentActions,
+    /// `user.blogEntries`
+    UserBlogEntries,
+    /// `user.friends`
+    UserFriends,
+    /// `user.info`
+    UserInfo,
+    /// `user.ratedList`
+    UserRatedList,
+    /// `user.rating`
+    UserRating,
+    /// `user.status`
+    UserStatus,
+}
+
+impl CFMethod {
+    /// Name of the method as used in API urls (eg. `"user.info"`).
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CFMethod::BlogEntryComments => "blogEntry.comments",
+            CFMethod::BlogEntryView => "blogEntry.view",
+            CFMethod::ContestHacks => "contest.hacks",
+            CFMethod::ContestList => "contest.list",
+            CFMethod::ContestRatingChanges => "contest.ratingChanges",
+            CFMethod::ContestStandings => "contest.standings",
+            CFMethod::ContestStatus => "contest.status",
+            CFMethod::ProblemsetProblems => "problemset.problems",
+            CFMethod::ProblemsetRecentStatus => "problemset.recentStatus",
+            CFMethod::RecentActions => "recentActions",
+            CFMethod::UserBlogEntries => "user.blogEntries",
+            CFMethod::UserFriends => "user.friends",
+            CFMethod::UserInfo => "user.info",
+            CFMethod::UserRatedList => "user.ratedList",
+            CFMethod::UserRating => "user.rating",
+            CFMethod::UserStatus => "user.status",
+        }
+    }
+}
+
 /// Trait implemented by any type which can be sent as a request to the
 /// Codeforces API.
 ///
@@ -785,10 +1059,22 @@ pub trait CFAPIRequestable {
     /// onto URL query parameters. Used internally and not much use for most
     /// people.
     fn query_params(&self) -> Vec<(&'static str, String)>;
+    /// Method which returns the API method the request is sent to (eg.
+    /// [`CFMethod::UserInfo`]).
+    fn method(&self) -> CFMethod;
     /// Method which returns a str slice of the method name (eg. "user.info").
     /// Used internally and not much use for most
     /// people.
-    fn method_name(&self) -> &'static str;
+    fn method_name(&self) -> &'static str {
+        self.method().as_str()
+    }
+    /// Method which checks the request for parameters which Codeforces is
+    /// known to reject, so that an [`Error::InvalidParameter`] can be
+    /// returned without sending anything. Used internally and not much use
+    /// for most people.
+    fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
     /// Fetch response from Codeforces servers.
     ///
     /// # Examples
@@ -861,12 +1147,10 @@ impl CFAPIRequestable for CFBlogEntryCommand {
         res
     }
 
-    fn method_name(&self) -> &'static str {
+    fn method(&self) -> CFMethod {
         match self {
-            CFBlogEntryCommand::Comments { blog_entry_id: _ } => {
-                "blogEntry.comments"
-            }
-            CFBlogEntryCommand::View { blog_entry_id: _ } => "blogEntry.view",
+            CFBlogEntryCommand::Comments { .. } => CFMethod::BlogEntryComments,
+            CFBlogEntryCommand::View { .. } => CFMethod::BlogEntryView,
         }
     }
 
@@ -962,13 +1246,15 @@ impl CFAPIRequestable for CFContestCommand {
         res
     }
 
-    fn method_name(&self) -> &'static str {
+    fn method(&self) -> CFMethod {
         match self {
-            CFContestCommand::Hacks { .. } => "contest.hacks",
-            CFContestCommand::List { .. } => "contest.list",
-            CFContestCommand::RatingChanges { .. } => "contest.ratingChanges",
-            CFContestCommand::Standings { .. } => "contest.standings",
-            CFContestCommand::Status { .. } => "contest.status",
+            CFContestCommand::Hacks { .. } => CFMethod::ContestHacks,
+            CFContestCommand::List { .. } => CFMethod::ContestList,
+            CFContestCommand::RatingChanges { .. } => {
+                CFMethod::ContestRatingChanges
+            }
+            CFContestCommand::Standings { .. } => CFMethod::ContestStandings,
+            CFContestCommand::Status { .. } => CFMethod::ContestStatus,
         }
     }
 
@@ -1017,11 +1303,13 @@ impl CFAPIRequestable for CFProblemsetCommand {
         res
     }
 
-    fn method_name(&self) -> &'static str {
+    fn method(&self) -> CFMethod {
         match self {
-            CFProblemsetCommand::Problems { .. } => "problemset.problems",
+            CFProblemsetCommand::Problems { .. } => {
+                CFMethod::ProblemsetProblems
+            }
             CFProblemsetCommand::RecentStatus { .. } => {
-                "problemset.recentStatus"
+                CFMethod::ProblemsetRecentStatus
             }
         }
     }
@@ -1045,13 +1333,11 @@ impl CFAPIRequestable for CFProblemsetCommand {
 
 impl CFAPIRequestable for CFRecentActionsCommand {
     fn query_params(&self) -> Vec<(&'static str, String)> {
-        let mut res = vec![];
-        res.push(("maxCount", self.max_count.to_string()));
-        res
+        vec![("maxCount", self.max_count.to_string())]
     }
 
-    fn method_name(&self) -> &'static str {
-        "recentActions"
+    fn method(&self) -> CFMethod {
+        CFMethod::RecentActions
     }
 
     fn get(
@@ -1125,14 +1411,25 @@ impl CFAPIRequestable for CFUserCommand {
         res
     }
 
-    fn method_name(&self) -> &'static str {
+    fn method(&self) -> CFMethod {
         match self {
-            CFUserCommand::BlogEntries { .. } => "user.blogEntries",
-            CFUserCommand::Friends { .. } => "user.friends",
-            CFUserCommand::Info { .. } => "user.info",
-            CFUserCommand::RatedList { .. } => "user.ratedList",
-            CFUserCommand::Rating { .. } => "user.rating",
-            CFUserCommand::Status { .. } => "user.status",
+            CFUserCommand::BlogEntries { .. } => CFMethod::UserBlogEntries,
+            CFUserCommand::Friends { .. } => CFMethod::UserFriends,
+            CFUserCommand::Info { .. } => CFMethod::UserInfo,
+            CFUserCommand::RatedList { .. } => CFMethod::UserRatedList,
+            CFUserCommand::Rating { .. } => CFMethod::UserRating,
+            CFUserCommand::Status { .. } => CFMethod::UserStatus,
+        }
+    }
+
+    fn validate(&self) -> Result<(), Error> {
+        match self {
+            CFUserCommand::Info { handles } if handles.is_empty() => {
+                Err(Error::InvalidParameter(
+                    "user.info requires at least one handle",
+                ))
+            }
+            _ => Ok(()),
         }
     }
 
@@ -1158,42 +1455,95 @@ impl CFAPIRequestable for CFUserCommand {
 ///
 /// Used internally to provide
 /// [`problem.fetch_testcases()`](responses::CFProblem::fetch_testcases).
+/// The request is not spaced out from other requests; use
+/// [`CFClient::fetch_testcases`](super::client::CFClient::fetch_testcases) to
+/// respect the client's minimum interval.
 #[cfg(feature = "use_testcase_fetcher")]
 pub fn fetch_testcases_for_problem(
     contest_id: &i64,
     problem_index: &str,
 ) -> Result<Vec<String>, Error> {
-    // construct problem url.
-    let url = "https://codeforces.com/contest/".to_string()
+    let bytes =
+        get_url_bytes(&problem_url(WEB_STUB, contest_id, problem_index))?;
+    parse_testcases(&bytes)
+}
+
+/// Constructs the url of a problem page on the Codeforces website (or a
+/// mirror of it at `web_stub`).
+#[cfg(feature = "use_testcase_fetcher")]
+pub(crate) fn problem_url(
+    web_stub: &str,
+    contest_id: &i64,
+    problem_index: &str,
+) -> String {
+    web_stub.to_string()
+        + "contest/"
         + &contest_id.to_string()
         + "/problem/"
-        + &problem_index.to_string();
-    match get_url(&url) {
-        // if fetch was successful, then read response.
-        Ok(res) => {
-            let document = Document::from_read(res).unwrap();
-            // older problems use <br> instead of text \n chars in the
-            // testcases. These are replaced by a regex for consistency.
-            lazy_static! {
-                static ref RE: Regex = Regex::new(r"(<br>|<br/>)").unwrap();
-            }
-            let testcases: Vec<String> = document
-                .find(Descendant(Class("input"), Name("pre")))
-                .map(|e| e.inner_html())
-                .map(|e| RE.replace_all(&e, "\n").into())
+        + problem_index
+}
+
+/// Scrapes the input testcases out of the raw bytes of a fetched problem page.
+/// Each `<pre>` in an input block is one sample test.
+///
+/// Newer problems wrap every line of a sample in its own
+/// `<div class="test-example-line">` element inside the `<pre>`, in which case
+/// the lines are joined back into one multi-line input.
+///
+/// The page is decoded as UTF-8 lossily, so any malformed bytes are replaced
+/// with `U+FFFD` rather than failing the whole scrape.
+#[cfg(feature = "use_testcase_fetcher")]
+pub(crate) fn parse_testcases(bytes: &[u8]) -> Result<Vec<String>, Error> {
+    let document = Document::from(&*String::from_utf8_lossy(bytes));
+    // older problems use <br> instead of text \n chars in the
+    // testcases. These are replaced by a regex for consistency.
+    lazy_static! {
+        static ref RE: Regex = Regex::new(r"(<br>|<br/>)").unwrap();
+    }
+    let testcases: Vec<String> = document
+        .find(Descendant(Class("input"), Name("pre")))
+        .map(|pre| {
+            let lines: Vec<String> = pre
+                .find(Class("test-example-line"))
+                .map(|line| line.text() + "\n")
                 .collect();
-            if testcases.is_empty() {
-                Err(Error::Testcases(
-                    "No testcase input found for this \
-                        problem.",
-                ))
+            if lines.is_empty() {
+                RE.replace_all(&pre.inner_html(), "\n").into()
             } else {
-                Ok(testcases)
+                lines.concat()
             }
-        }
-        // if fetch unsuccessful, then wrap `reqwest::Error` in custom Error.
-        Err(e) => Err(Error::Http(e)),
+        })
+        .collect();
+    if testcases.is_empty() {
+        Err(Error::Testcases(
+            "No testcase input found for this \
+                problem.",
+        ))
+    } else {
+        Ok(testcases)
+    }
+}
+
+/// Writes each testcase to its own file in `dir`, named
+/// `{problem_index}_{n}.txt` where `n` is the 1-based testcase number. The
+/// directory is created if it does not already exist.
+///
+/// Returns the paths of the written files in testcase order.
+#[cfg(feature = "use_testcase_fetcher")]
+pub fn write_testcases_to_dir<P: AsRef<Path>>(
+    dir: P,
+    problem_index: &str,
+    testcases: &[String],
+) -> Result<Vec<PathBuf>, Error> {
+    let dir = dir.as_ref();
+    fs::create_dir_all(dir)?;
+    let mut paths = Vec::with_capacity(testcases.len());
+    for (i, testcase) in testcases.iter().enumerate() {
+        let path = dir.join(format!("{}_{}.txt", problem_index, i + 1));
+        fs::write(&path, testcase)?;
+        paths.push(path);
     }
+    Ok(paths)
 }
 
 #[cfg(feature = "use_testcase_fetcher")]
@@ -1206,25 +1556,267 @@ impl responses::CFProblem {
     /// Codeforces is not returned. However, in future this could be
     /// implemented relatively easily.
     ///
-    /// Uses [`fetch_testcases_for_problem`] under the hood.
+    /// Uses [`fetch_testcases_for_problem`] under the hood, so the request is
+    /// not spaced out from other requests; use
+    /// [`CFClient::fetch_testcases`](super::client::CFClient::fetch_testcases)
+    /// to respect the client's minimum interval.
     pub fn fetch_testcases(&mut self) -> Result<Vec<String>, Error> {
-        if self.contest_id.is_none() {
-            return Err(Error::Testcases(
+        let testcases = get_url_bytes(&self.page_url(WEB_STUB)?)
+            .and_then(|bytes| parse_testcases(&bytes));
+        self.set_input_testcases(testcases)
+    }
+
+    /// Constructs the url of the problem page under `web_stub`, which
+    /// requires both the `contest_id` and `index` fields.
+    pub(crate) fn page_url(&self, web_stub: &str) -> Result<String, Error> {
+        match (&self.contest_id, &self.index) {
+            (None, _) => Err(Error::Testcases(
                 "problem.contest_id field is \
                     required.",
-            ));
-        }
-        if self.index.is_none() {
-            return Err(Error::Testcases("problem.index field is required."));
+            )),
+            (_, None) => {
+                Err(Error::Testcases("problem.index field is required."))
+            }
+            (Some(contest_id), Some(index)) => {
+                Ok(problem_url(web_stub, contest_id, index))
+            }
         }
-        let testcases = fetch_testcases_for_problem(
-            &self.contest_id.unwrap(),
-            &self.index.as_ref().unwrap(),
-        );
-        // if getting testcases was successful, then set self.input_testcases.
+    }
+
+    /// If getting testcases was successful, then set `self.input_testcases`.
+    pub(crate) fn set_input_testcases(
+        &mut self,
+        testcases: Result<Vec<String>, Error>,
+    ) -> Result<Vec<String>, Error> {
         if let Ok(ref v) = testcases {
             self.input_testcases = Some(v.to_vec());
         }
         testcases
     }
 }
+
+/// Extra utility function which webscrapes a contest page to get the list of
+/// contest materials (eg. statements, tutorials or test data) attached to it.
+///
+/// Returns a Vec of `(label, url)` pairs, one per attached material. An empty
+/// Vec is returned if the contest has no materials.
+///
+/// The request is not spaced out from other requests; use
+/// [`CFClient::fetch_contest_materials`](super::client::CFClient::fetch_contest_materials)
+/// to respect the client's minimum interval.
+#[cfg(feature = "use_testcase_fetcher")]
+pub fn fetch_contest_materials(
+    contest_id: &i64,
+) -> Result<Vec<(String, String)>, Error> {
+    let bytes = get_url_bytes(&contest_url(WEB_STUB, contest_id))?;
+    Ok(parse_contest_materials(&bytes))
+}
+
+/// Constructs the url of a contest page on the Codeforces website (or a
+/// mirror of it at `web_stub`). Contests with an id of at least 100000 are
+/// gym contests.
+#[cfg(feature = "use_testcase_fetcher")]
+pub(crate) fn contest_url(web_stub: &str, contest_id: &i64) -> String {
+    if *contest_id >= 100000 {
+        web_stub.to_string() + "gym/" + &contest_id.to_string()
+    } else {
+        web_stub.to_string() + "contest/" + &contest_id.to_string()
+    }
+}
+
+/// Scrapes the links listed in the "Contest materials" sidebar box out of
+/// the raw bytes of a fetched contest page.
+#[cfg(feature = "use_testcase_fetcher")]
+pub(crate) fn parse_contest_materials(bytes: &[u8]) -> Vec<(String, String)> {
+    let document = Document::from(&*String::from_utf8_lossy(bytes));
+    let mut materials = vec![];
+    for sidebox in document.find(Class("sidebox")) {
+        let is_materials = sidebox
+            .find(Class("caption"))
+            .next()
+            .is_some_and(|c| c.text().contains("materials"));
+        if !is_materials {
+            continue;
+        }
+        for link in sidebox.find(Name("a")) {
+            let label = link.text().trim().to_string();
+            let href = match link.attr("href") {
+                Some(href) if !label.is_empty() => href,
+                _ => continue,
+            };
+            let url = if href.starts_with('/') {
+                "https://codeforces.com".to_string() + href
+            } else {
+                href.to_string()
+            };
+            materials.push((label, url));
+        }
+    }
+    materials
+}
+
+/// Details of a contest scraped from its page, as returned by
+/// `parse_contest_about()`.
+#[cfg(feature = "use_testcase_fetcher")]
+#[derive(Debug, Default, PartialEq, Eq)]
+pub(crate) struct ContestAbout {
+    pub(crate) description: Option<String>,
+    pub(crate) website_url: Option<String>,
+    pub(crate) prepared_by: Option<String>,
+}
+
+/// Scrapes the "About Contest" sidebar box out of the raw bytes of a fetched
+/// contest page.
+///
+/// Links to user profiles are taken as the authors of the contest, the first
+/// external link is taken as its website and any remaining text lines make
+/// up its description.
+#[cfg(feature = "use_testcase_fetcher")]
+pub(crate) fn parse_contest_about(bytes: &[u8]) -> ContestAbout {
+    let document = Document::from(&*String::from_utf8_lossy(bytes));
+    let mut about = ContestAbout::default();
+    let sidebox = document.find(Class("sidebox")).find(|s| {
+        s.find(Class("caption"))
+            .next()
+            .is_some_and(|c| c.text().contains("About"))
+    });
+    let sidebox = match sidebox {
+        Some(s) => s,
+        None => return about,
+    };
+    let mut authors = vec![];
+    for link in sidebox.find(Name("a")) {
+        match link.attr("href") {
+            Some(href) if href.starts_with("/profile/") => {
+                authors.push(link.text().trim().to_string());
+            }
+            Some(href)
+                if href.starts_with("http")
+                    && !href.contains("codeforces.com")
+                    && about.website_url.is_none() =>
+            {
+                about.website_url = Some(href.to_string());
+            }
+            _ => {}
+        }
+    }
+    if !authors.is_empty() {
+        about.prepared_by = Some(authors.join(", "));
+    }
+    let caption = sidebox
+        .find(Class("caption"))
+        .next()
+        .map(|c| c.text())
+        .unwrap_or_default();
+    let description: Vec<String> = sidebox
+        .text()
+        .replacen(&caption, "", 1)
+        .lines()
+        .map(|l| l.trim().to_string())
+        .filter(|l| {
+            !l.is_empty()
+                && !l.starts_with("Prepared by")
+                && !l.starts_with("Website")
+                && !authors.contains(l)
+        })
+        .collect();
+    if !description.is_empty() {
+        about.description = Some(description.join("\n"));
+    }
+    about
+}
+
+#[cfg(feature = "use_testcase_fetcher")]
+impl responses::CFContest {
+    /// Extra method which scrapes the contest page to fill in the
+    /// `description`, `website_url` and `prepared_by` fields where they are
+    /// `None`. Mostly useful for gym contests, where the API often omits them
+    /// even though the contest page shows them.
+    ///
+    /// Fields which are already set are left untouched. Scraping is a best
+    /// effort, so fields may remain `None` if the page does not show them.
+    ///
+    /// The request is not spaced out from other requests; use
+    /// [`CFClient::enrich_contest`](super::client::CFClient::enrich_contest)
+    /// to respect the client's minimum interval.
+    pub fn enrich_from_web(&mut self) -> Result<(), Error> {
+        let bytes = get_url_bytes(&contest_url(WEB_STUB, &self.id))?;
+        self.enrich_from_page(&bytes);
+        Ok(())
+    }
+
+    /// Fill in the fields left `None` from the raw bytes of the fetched
+    /// contest page.
+    pub(crate) fn enrich_from_page(&mut self, bytes: &[u8]) {
+        let about = parse_contest_about(bytes);
+        if self.description.is_none() {
+            self.description = about.description;
+        }
+        if self.website_url.is_none() {
+            self.website_url = about.website_url;
+        }
+        if self.prepared_by.is_none() {
+            self.prepared_by = about.prepared_by;
+        }
+    }
+}
+
+/// Constructs the url of a page of the submissions of a user on the
+/// Codeforces website (or a mirror of it at `web_stub`), percent-encoding the
+/// handle.
+#[cfg(feature = "use_testcase_fetcher")]
+pub(crate) fn submissions_url(
+    web_stub: &str,
+    handle: &str,
+    page: usize,
+) -> Result<String, Error> {
+    let mut url = reqwest::Url::parse(web_stub)
+        .map_err(|_| Error::InvalidParameter("web url is not a valid url"))?;
+    url.path_segments_mut()
+        .map_err(|_| Error::InvalidParameter("web url cannot be a base"))?
+        .pop_if_empty()
+        .extend(&["submissions", handle, "page", &page.to_string()]);
+    Ok(url.into())
+}
+
+/// Scrapes the raw bytes of a fetched submissions page, returning the ids of
+/// all listed submissions and the `(contest_id, index)` pairs of the problems
+/// of accepted ones.
+#[cfg(feature = "use_testcase_fetcher")]
+pub(crate) fn parse_submissions_page(
+    bytes: &[u8],
+) -> (Vec<i64>, HashSet<(i64, String)>) {
+    lazy_static! {
+        static ref RE: Regex = Regex::new(
+            r"^/(?:(?:contest|gym)/(\d+)/problem|problemset/problem/(\d+))/(\w+)$"
+        )
+        .unwrap();
+    }
+    let document = Document::from(&*String::from_utf8_lossy(bytes));
+    let mut ids = vec![];
+    let mut solved = HashSet::new();
+    for row in document.find(Attr("data-submission-id", ())) {
+        match row
+            .attr("data-submission-id")
+            .and_then(|id| id.parse().ok())
+        {
+            Some(id) => ids.push(id),
+            None => continue,
+        }
+        if row.find(Class("verdict-accepted")).next().is_none() {
+            continue;
+        }
+        let problem = row
+            .find(Name("a"))
+            .filter_map(|a| a.attr("href"))
+            .find_map(|href| RE.captures(href));
+        if let Some(caps) = problem {
+            let contest_id = caps.get(1).or_else(|| caps.get(2));
+            if let Some(Ok(contest_id)) = contest_id.map(|c| c.as_str().parse())
+            {
+                solved.insert((contest_id, caps[3].to_string()));
+            }
+        }
+    }
+    (ids, solved)
+}
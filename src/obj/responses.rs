@@ -1,7 +1,18 @@
 //! Contains the structs etc. which are returned by the Codeforces API
 //! following a request.
+//!
+//! By default, fields returned by the API which are not modelled here are
+//! silently ignored. Enabling the `strict` feature makes deserialization fail
+//! on unknown fields instead, which is useful for detecting API schema drift.
+//!
+//! Similarly, enums parsed from strings (eg. [`CFSubmissionVerdict`]) have an
+//! `Unknown(String)` variant holding any value which is not modelled here, so
+//! that new values introduced by Codeforces do not fail the whole response.
 
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::time::Duration;
 
 #[cfg(feature = "serde_yaml")]
 use std::fmt;
@@ -35,6 +46,7 @@ impl fmt::Display for CFResponseStatus {
 /// a [`CFResult`] (in a result) and not this type. Internally, [`CFResponse`]
 /// is used as a wrapper to handle errors and serialization more easily.
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct CFResponse {
     pub status: CFResponseStatus,
     pub result: Option<CFResult>,
@@ -108,10 +120,70 @@ impl fmt::Display for CFResult {
     }
 }
 
+impl CFResult {
+    /// Returns the name of the variant held (eg. `"CFUserVec"`), useful for
+    /// reporting which kind of result was received when it was unexpected.
+    pub fn variant_name(&self) -> &'static str {
+        match self {
+            CFResult::CFCommentVec(_) => "CFCommentVec",
+            CFResult::CFBlogEntry(_) => "CFBlogEntry",
+            CFResult::CFHackVec(_) => "CFHackVec",
+            CFResult::CFContestVec(_) => "CFContestVec",
+            CFResult::CFRatingChangeVec(_) => "CFRatingChangeVec",
+            CFResult::CFContestStandings(_) => "CFContestStandings",
+            CFResult::CFSubmissionVec(_) => "CFSubmissionVec",
+            CFResult::CFProblemset(_) => "CFProblemset",
+            CFResult::CFRecentActionVec(_) => "CFRecentActionVec",
+            CFResult::CFBlogEntryVec(_) => "CFBlogEntryVec",
+            CFResult::CFFriends(_) => "CFFriends",
+            CFResult::CFUserVec(_) => "CFUserVec",
+        }
+    }
+}
+
+/// Implements `TryFrom<CFResult>` for the type held by each variant, so that
+/// eg. `let users: Vec<CFUser> = result.try_into()?` works.
+macro_rules! impl_try_from_cf_result {
+    ($($variant:ident => $inner:ty),* $(,)?) => {
+        $(
+            impl std::convert::TryFrom<CFResult> for $inner {
+                type Error = super::error::Error;
+
+                /// Returns [`Error::UnexpectedResponseType`](super::error::Error::UnexpectedResponseType)
+                /// if the result holds a different variant.
+                fn try_from(result: CFResult) -> Result<Self, Self::Error> {
+                    match result {
+                        CFResult::$variant(v) => Ok(v),
+                        r => Err(super::error::Error::UnexpectedResponseType(
+                            r.variant_name(),
+                        )),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_try_from_cf_result! {
+    CFCommentVec => Vec<CFComment>,
+    CFBlogEntry => CFBlogEntry,
+    CFHackVec => Vec<CFHack>,
+    CFContestVec => Vec<CFContest>,
+    CFRatingChangeVec => Vec<CFRatingChange>,
+    CFContestStandings => CFContestStandings,
+    CFSubmissionVec => Vec<CFSubmission>,
+    CFProblemset => CFProblemset,
+    CFRecentActionVec => Vec<CFRecentAction>,
+    CFBlogEntryVec => Vec<CFBlogEntry>,
+    CFFriends => Vec<String>,
+    CFUserVec => Vec<CFUser>,
+}
+
 /// Struct representing a Codeforces
 /// [user](https://codeforces.com/apiHelp/objects#User).
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct CFUser {
     pub handle: String,
     pub email: Option<String>,
@@ -145,10 +217,156 @@ impl fmt::Display for CFUser {
     }
 }
 
+/// Window (in seconds) used by [`CFUser::online_now`], 5 minutes.
+pub const CF_ONLINE_WINDOW_SECONDS: i64 = 300;
+
+impl CFUser {
+    /// Returns `true` if the user was last online no more than `seconds`
+    /// seconds before the UNIX time `now`.
+    pub fn is_online_within(&self, seconds: i64, now: i64) -> bool {
+        now - self.last_online_time_seconds <= seconds
+    }
+
+    /// Returns `true` if the user was online within the last
+    /// [`CF_ONLINE_WINDOW_SECONDS`] before the UNIX time `now`.
+    pub fn online_now(&self, now: i64) -> bool {
+        self.is_online_within(CF_ONLINE_WINDOW_SECONDS, now)
+    }
+
+    /// Returns the user's current rank parsed as a [`CFRank`], or `None` if
+    /// the user is unrated or has a rank not known to this crate.
+    pub fn rank_enum(&self) -> Option<CFRank> {
+        self.rank.as_deref().and_then(|r| r.parse().ok())
+    }
+
+    /// Returns the url of the user's avatar, with protocol-relative urls
+    /// (eg. `//userpic.codeforces.org/...`) made absolute using `https:`.
+    pub fn avatar_url(&self) -> String {
+        absolute_url(&self.avatar)
+    }
+
+    /// Returns the url of the user's title photo, with protocol-relative urls
+    /// made absolute using `https:`.
+    pub fn title_photo_url(&self) -> String {
+        absolute_url(&self.title_photo)
+    }
+}
+
+/// Rank of a Codeforces user (eg. `"candidate master"`), ordered from lowest
+/// to highest rating.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum CFRank {
+    Newbie,
+    Pupil,
+    Specialist,
+    Expert,
+    CandidateMaster,
+    Master,
+    InternationalMaster,
+    Grandmaster,
+    InternationalGrandmaster,
+    LegendaryGrandmaster,
+}
+
+impl CFRank {
+    /// Returns the rank as written by Codeforces (eg. `"candidate master"`).
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CFRank::Newbie => "newbie",
+            CFRank::Pupil => "pupil",
+            CFRank::Specialist => "specialist",
+            CFRank::Expert => "expert",
+            CFRank::CandidateMaster => "candidate master",
+            CFRank::Master => "master",
+            CFRank::InternationalMaster => "international master",
+            CFRank::Grandmaster => "grandmaster",
+            CFRank::InternationalGrandmaster => "international grandmaster",
+            CFRank::LegendaryGrandmaster => "legendary grandmaster",
+        }
+    }
+}
+
+/// Color in which Codeforces displays a rating band, used for both user
+/// ranks and problem difficulties.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CFRankColor {
+    Gray,
+    Green,
+    Cyan,
+    Blue,
+    Violet,
+    Orange,
+    Red,
+}
+
+impl CFRankColor {
+    /// Returns the color of the band containing `rating`.
+    pub fn from_rating(rating: i64) -> CFRankColor {
+        match rating {
+            r if r < 1200 => CFRankColor::Gray,
+            r if r < 1400 => CFRankColor::Green,
+            r if r < 1600 => CFRankColor::Cyan,
+            r if r < 1900 => CFRankColor::Blue,
+            r if r < 2100 => CFRankColor::Violet,
+            r if r < 2400 => CFRankColor::Orange,
+            _ => CFRankColor::Red,
+        }
+    }
+}
+
+impl CFRank {
+    /// Returns the color in which Codeforces displays users of this rank.
+    pub fn color(&self) -> CFRankColor {
+        match self {
+            CFRank::Newbie => CFRankColor::Gray,
+            CFRank::Pupil => CFRankColor::Green,
+            CFRank::Specialist => CFRankColor::Cyan,
+            CFRank::Expert => CFRankColor::Blue,
+            CFRank::CandidateMaster => CFRankColor::Violet,
+            CFRank::Master | CFRank::InternationalMaster => CFRankColor::Orange,
+            CFRank::Grandmaster
+            | CFRank::InternationalGrandmaster
+            | CFRank::LegendaryGrandmaster => CFRankColor::Red,
+        }
+    }
+}
+
+impl std::str::FromStr for CFRank {
+    type Err = super::error::Error;
+
+    /// Parses a rank as written by Codeforces, ignoring case.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "newbie" => Ok(CFRank::Newbie),
+            "pupil" => Ok(CFRank::Pupil),
+            "specialist" => Ok(CFRank::Specialist),
+            "expert" => Ok(CFRank::Expert),
+            "candidate master" => Ok(CFRank::CandidateMaster),
+            "master" => Ok(CFRank::Master),
+            "international master" => Ok(CFRank::InternationalMaster),
+            "grandmaster" => Ok(CFRank::Grandmaster),
+            "international grandmaster" => Ok(CFRank::InternationalGrandmaster),
+            "legendary grandmaster" => Ok(CFRank::LegendaryGrandmaster),
+            _ => Err(super::error::Error::InvalidParameter("unknown rank")),
+        }
+    }
+}
+
+/// Prefixes a protocol-relative url (starting with `//`) with `https:`,
+/// leaving any other url untouched.
+fn absolute_url(url: &str) -> String {
+    if url.starts_with("//") {
+        format!("https:{}", url)
+    } else {
+        url.to_string()
+    }
+}
+
 /// Struct representing a Codeforces
 /// [blog entry](https://codeforces.com/apiHelp/objects#BlogEntry).
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct CFBlogEntry {
     pub id: i64,
     pub original_locale: String,
@@ -174,10 +392,27 @@ impl fmt::Display for CFBlogEntry {
     }
 }
 
+impl CFBlogEntry {
+    /// Returns `true` if the blog entry's content was returned by the API.
+    ///
+    /// Content can be missing, for example, in the requested locale or in
+    /// entries returned by `user.blogEntries`.
+    pub fn has_content(&self) -> bool {
+        self.content.is_some()
+    }
+
+    /// Returns the content of the blog entry, falling back to its title when
+    /// no content is present.
+    pub fn content_or_title(&self) -> &str {
+        self.content.as_deref().unwrap_or(&self.title)
+    }
+}
+
 /// Struct representing a Codeforces blog entry
 /// [comment](https://codeforces.com/apiHelp/objects#Comment).
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct CFComment {
     pub id: i64,
     pub creation_time_seconds: i64,
@@ -199,10 +434,23 @@ impl fmt::Display for CFComment {
     }
 }
 
+/// Returns the `n` highest-rated of `comments`, highest first. Comments with
+/// equal ratings are ordered from oldest to newest.
+pub fn top_comments(mut comments: Vec<CFComment>, n: usize) -> Vec<CFComment> {
+    comments.sort_by(|a, b| {
+        b.rating
+            .cmp(&a.rating)
+            .then(a.creation_time_seconds.cmp(&b.creation_time_seconds))
+    });
+    comments.truncate(n);
+    comments
+}
+
 /// Struct representing a Codeforces
 /// [recent action](https://codeforces.com/apiHelp/objects#RecentAction).
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct CFRecentAction {
     pub time_seconds: i64,
     pub blog_entry: Option<CFBlogEntry>,
@@ -220,10 +468,38 @@ impl fmt::Display for CFRecentAction {
     }
 }
 
+/// Kind of a [`CFRecentAction`], depending on which of its fields are
+/// present.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CFRecentActionKind {
+    /// Only a blog entry is present.
+    NewBlog,
+    /// Only a comment is present.
+    NewComment,
+    /// Both a blog entry and a comment (on that blog entry) are present.
+    Both,
+    /// Neither a blog entry nor a comment is present.
+    Unknown,
+}
+
+impl CFRecentAction {
+    /// Classifies the action by which of `blog_entry` and `comment` are
+    /// present.
+    pub fn kind(&self) -> CFRecentActionKind {
+        match (&self.blog_entry, &self.comment) {
+            (Some(_), None) => CFRecentActionKind::NewBlog,
+            (None, Some(_)) => CFRecentActionKind::NewComment,
+            (Some(_), Some(_)) => CFRecentActionKind::Both,
+            (None, None) => CFRecentActionKind::Unknown,
+        }
+    }
+}
+
 /// Struct representing a Codeforces
 /// [rating change](https://codeforces.com/apiHelp/objects#RatingChange).
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct CFRatingChange {
     pub contest_id: i64,
     pub contest_name: String,
@@ -245,13 +521,87 @@ impl fmt::Display for CFRatingChange {
     }
 }
 
+/// Converts a rating history (as returned by `user.rating`) into
+/// `(rating_update_time_seconds, new_rating)` pairs sorted by time, ready to
+/// be plotted.
+pub fn rating_timeline(changes: &[CFRatingChange]) -> Vec<(i64, i64)> {
+    let mut timeline: Vec<(i64, i64)> = changes
+        .iter()
+        .map(|c| (c.rating_update_time_seconds, c.new_rating))
+        .collect();
+    timeline.sort_unstable();
+    timeline
+}
+
+/// Finds the id of the contest named `name` in a rating history (as returned
+/// by `user.rating`), ignoring case and surrounding whitespace. Returns `None`
+/// if no rating change is for a contest of that name.
+pub fn find_contest_id_by_name(
+    changes: &[CFRatingChange],
+    name: &str,
+) -> Option<i64> {
+    let name = name.trim().to_lowercase();
+    changes
+        .iter()
+        .find(|c| c.contest_name.trim().to_lowercase() == name)
+        .map(|c| c.contest_id)
+}
+
+/// Returns the contests whose name contains `query`, ignoring case, in their
+/// original order.
+pub fn contests_matching_name(
+    contests: Vec<CFContest>,
+    query: &str,
+) -> Vec<CFContest> {
+    let query = query.to_lowercase();
+    contests
+        .into_iter()
+        .filter(|c| c.name.to_lowercase().contains(&query))
+        .collect()
+}
+
+/// One row of a post-contest rating leaderboard, as returned by
+/// [`rating_leaderboard`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CFRatingDelta {
+    pub handle: String,
+    pub rank: i64,
+    pub old_rating: i64,
+    pub new_rating: i64,
+    /// `new_rating - old_rating`.
+    pub delta: i64,
+}
+
+/// Converts the rating changes of a contest (as returned by
+/// `contest.ratingChanges`) into a leaderboard sorted by rank, with the
+/// rating delta of each participant. Ties in rank are ordered by handle.
+pub fn rating_leaderboard(changes: &[CFRatingChange]) -> Vec<CFRatingDelta> {
+    let mut leaderboard: Vec<CFRatingDelta> = changes
+        .iter()
+        .map(|c| CFRatingDelta {
+            handle: c.handle.clone(),
+            rank: c.rank,
+            old_rating: c.old_rating,
+            new_rating: c.new_rating,
+            delta: c.new_rating - c.old_rating,
+        })
+        .collect();
+    leaderboard.sort_by(|a, b| {
+        a.rank.cmp(&b.rank).then_with(|| a.handle.cmp(&b.handle))
+    });
+    leaderboard
+}
+
 /// Contest type returned by Codeforces API (eg. IOI, ICPC).
-#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
 pub enum CFContestType {
     #[serde(rename = "CF")]
     Codeforces,
     IOI,
     ICPC,
+    /// Any value not (yet) known to this crate, preserved as returned.
+    #[serde(untagged)]
+    Unknown(String),
 }
 
 #[cfg(feature = "serde_yaml")]
@@ -266,7 +616,7 @@ impl fmt::Display for CFContestType {
 }
 
 /// Contest phase returned by Codeforces API (eg. PendingSystemTest).
-#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum CFContestPhase {
     Before,
@@ -274,6 +624,9 @@ pub enum CFContestPhase {
     PendingSystemTest,
     SystemTest,
     Finished,
+    /// Any value not (yet) known to this crate, preserved as returned.
+    #[serde(untagged)]
+    Unknown(String),
 }
 
 #[cfg(feature = "serde_yaml")]
@@ -287,11 +640,27 @@ impl fmt::Display for CFContestPhase {
     }
 }
 
+impl CFContestPhase {
+    /// Returns all known contest phases, excluding `Unknown`. Useful for
+    /// building filters or exhaustive tests.
+    pub fn all_variants() -> &'static [CFContestPhase] {
+        const ALL: &[CFContestPhase] = &[
+            CFContestPhase::Before,
+            CFContestPhase::Coding,
+            CFContestPhase::PendingSystemTest,
+            CFContestPhase::SystemTest,
+            CFContestPhase::Finished,
+        ];
+        ALL
+    }
+}
+
 /// Struct representing the object returned by a
 /// [`contest.standings`](super::requests::CFContestCommand::Standings)
 /// request.
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct CFContestStandings {
     pub contest: CFContest,
     pub problems: Vec<CFProblem>,
@@ -309,10 +678,328 @@ impl fmt::Display for CFContestStandings {
     }
 }
 
+impl CFContestStandings {
+    /// Returns a normalized, comparable score for each row (in row order),
+    /// interpreted according to the contest's [`CFContestType`]:
+    ///
+    /// - `Codeforces`: the row's total points (including hacks), ties broken
+    ///   by penalty.
+    /// - `ICPC`: the number of solved problems (problem results with positive
+    ///   points), ties broken by penalty.
+    /// - `IOI`: the sum of the points scored on each problem, ties broken by
+    ///   penalty.
+    /// - Unknown contest types are treated like `Codeforces`.
+    ///
+    /// A greater [`CFNormalizedScore`] is a better placement, so sorting rows
+    /// by descending score re-ranks them consistently.
+    pub fn normalized_scores(&self) -> Vec<CFNormalizedScore> {
+        self.rows
+            .iter()
+            .map(|row| {
+                let score = match self.contest.contest_type {
+                    CFContestType::ICPC => {
+                        row.problem_results
+                            .iter()
+                            .filter(|r| r.points > 0.0)
+                            .count() as f64
+                    }
+                    CFContestType::IOI => {
+                        row.problem_results.iter().map(|r| r.points).sum()
+                    }
+                    CFContestType::Codeforces | CFContestType::Unknown(_) => {
+                        row.points
+                    }
+                };
+                CFNormalizedScore {
+                    score,
+                    penalty: row.penalty,
+                }
+            })
+            .collect()
+    }
+
+    /// Returns the rows of `self` whose rank or points differ from the row of
+    /// the same party in `previous`, including rows for parties which are not
+    /// present in `previous` at all.
+    ///
+    /// Parties are matched as in
+    /// [`dedup_rows`](CFContestStandings::dedup_rows), so that the same user
+    /// appearing both as a contestant and as a virtual participant is tracked
+    /// as two parties.
+    pub fn changed_rows(
+        &self,
+        previous: &CFContestStandings,
+    ) -> Vec<&CFRanklistRow> {
+        let previous: HashMap<PartyKey, &CFRanklistRow> =
+            previous.rows.iter().map(|r| (r.party.key(), r)).collect();
+        self.rows
+            .iter()
+            .filter(|r| match previous.get(&r.party.key()) {
+                Some(p) => p.rank != r.rank || p.points != r.points,
+                None => true,
+            })
+            .collect()
+    }
+
+    /// Returns the rank movement of each party between an `earlier` snapshot
+    /// of the standings and `self`, as `earlier_rank - current_rank`, so a
+    /// positive delta means the party moved up.
+    ///
+    /// Parties are matched by their
+    /// [`display_name`](CFParty::display_name). Parties which are absent from
+    /// either snapshot have no movement to report and are left out.
+    pub fn rank_deltas(
+        &self,
+        earlier: &CFContestStandings,
+    ) -> HashMap<String, i64> {
+        let earlier: HashMap<String, i64> = earlier
+            .rows
+            .iter()
+            .map(|r| (r.party.display_name(), r.rank))
+            .collect();
+        self.rows
+            .iter()
+            .filter_map(|r| {
+                let name = r.party.display_name();
+                let delta = earlier.get(&name)? - r.rank;
+                Some((name, delta))
+            })
+            .collect()
+    }
+
+    /// Counts, per problem index, the rows which solved the problem, ie. whose
+    /// result for it has positive points or a best submission time. Every
+    /// problem of the standings has an entry, even if nobody solved it.
+    pub fn solve_counts(&self) -> HashMap<String, usize> {
+        let mut counts: HashMap<String, usize> = HashMap::new();
+        for (i, problem) in self.problems.iter().enumerate() {
+            let solved = self
+                .rows
+                .iter()
+                .filter_map(|row| row.problem_results.get(i))
+                .filter(|r| {
+                    r.points > 0.0 || r.best_submission_time_seconds.is_some()
+                })
+                .count();
+            if let Some(ref index) = problem.index {
+                counts.insert(index.clone(), solved);
+            }
+        }
+        counts
+    }
+
+    /// Exports the top `top_n` rows of the standings as a Markdown table with
+    /// rank, party (see [`CFParty::display_name`]), points and penalty
+    /// columns, eg. for pasting into a GitHub issue.
+    ///
+    /// Any `|` in party names is escaped so that it does not break the table.
+    pub fn to_markdown(&self, top_n: usize) -> String {
+        let mut table = String::from(
+            "| Rank | Handle | Points | Penalty |\n|---:|---|---:|---:|\n",
+        );
+        for row in self.rows.iter().take(top_n) {
+            table += &format!(
+                "| {} | {} | {} | {} |\n",
+                row.rank,
+                row.party.display_name().replace('|', "\\|"),
+                row.points,
+                row.penalty
+            );
+        }
+        table
+    }
+
+    /// Fills in the `rating` of each of the standings' `problems` which lacks
+    /// one, using the problem with the same `(contest_id, index)` in
+    /// `problemset` (as returned by `problemset.problems`).
+    pub fn fill_problem_ratings(&mut self, problemset: &CFProblemset) {
+        let ratings: HashMap<(i64, &str), i64> = problemset
+            .problems
+            .iter()
+            .filter_map(|p| {
+                Some(((p.contest_id?, p.index.as_deref()?), p.rating?))
+            })
+            .collect();
+        for problem in self.problems.iter_mut().filter(|p| p.rating.is_none()) {
+            if let (Some(contest_id), Some(index)) =
+                (problem.contest_id, problem.index.as_deref())
+            {
+                problem.rating = ratings.get(&(contest_id, index)).copied();
+            }
+        }
+    }
+
+    /// Removes rows for parties which already appeared in an earlier row,
+    /// eg. after merging overlapping pages of standings, keeping the first
+    /// row of each party.
+    ///
+    /// Parties are identified by their participant type together with their
+    /// `team_id` if they are a team, or their sorted member handles
+    /// otherwise. The participant type is included since the same user can
+    /// appear both as a contestant and as a virtual participant.
+    pub fn dedup_rows(&mut self) {
+        let mut seen = std::collections::HashSet::new();
+        self.rows.retain(|row| seen.insert(row.party.key()));
+    }
+
+    /// Maps the handle of every member of every party in the standings to
+    /// the rank of their party, expanding teams into their individual
+    /// members.
+    pub fn member_ranks(&self) -> HashMap<String, i64> {
+        let mut ranks = HashMap::new();
+        for row in &self.rows {
+            for member in &row.party.members {
+                ranks.insert(member.handle.clone(), row.rank);
+            }
+        }
+        ranks
+    }
+
+    /// Rank of the party which the given handle is a member of, or `None` if
+    /// the handle does not appear in the standings. Handles are compared
+    /// case-insensitively, as they are on Codeforces.
+    pub fn rank_of(&self, handle: &str) -> Option<i64> {
+        self.row_of(handle).map(|row| row.rank)
+    }
+
+    /// Returns the per-problem breakdown of the party which the given handle
+    /// is a member of, or `None` if the handle does not appear in the
+    /// standings. Handles are compared case-insensitively.
+    pub fn scorecard_for(&self, handle: &str) -> Option<CFScorecard> {
+        let row = self.row_of(handle)?;
+        Some(CFScorecard {
+            rank: row.rank,
+            points: row.points,
+            penalty: row.penalty,
+            problems: self
+                .problems
+                .iter()
+                .zip(&row.problem_results)
+                .map(|(p, r)| (p.index.clone().unwrap_or_default(), r.clone()))
+                .collect(),
+        })
+    }
+
+    /// First row of the party which the given handle is a member of.
+    fn row_of(&self, handle: &str) -> Option<&CFRanklistRow> {
+        self.rows.iter().find(|row| {
+            row.party
+                .members
+                .iter()
+                .any(|m| m.handle.eq_ignore_ascii_case(handle))
+        })
+    }
+
+    /// First row in the standings with the given rank. When several parties
+    /// are tied they share a rank, and the ranks they skip (e.g. rank 2 after
+    /// two parties tied at rank 1) have no row, so `None` is returned.
+    pub fn party_at_rank(&self, rank: i64) -> Option<&CFRanklistRow> {
+        self.rows.iter().find(|row| row.rank == rank)
+    }
+
+    /// Returns a copy of the standings reduced to the problems with the given
+    /// indices (eg. `&["A", "C"]`), dropping all other problems and their
+    /// results from every row. Useful for cutting down the size of standings
+    /// kept for later use. Problems keep their original order.
+    pub fn project_problems(&self, indices: &[&str]) -> CFContestStandings {
+        let keep: Vec<bool> = self
+            .problems
+            .iter()
+            .map(|p| p.index.as_deref().is_some_and(|i| indices.contains(&i)))
+            .collect();
+        CFContestStandings {
+            contest: self.contest.clone(),
+            problems: project_by(&self.problems, &keep),
+            rows: self
+                .rows
+                .iter()
+                .map(|row| CFRanklistRow {
+                    party: row.party.clone(),
+                    rank: row.rank,
+                    points: row.points,
+                    penalty: row.penalty,
+                    successful_hack_count: row.successful_hack_count,
+                    unsuccessful_hack_count: row.unsuccessful_hack_count,
+                    problem_results: project_by(&row.problem_results, &keep),
+                    last_submission_time_seconds: row
+                        .last_submission_time_seconds,
+                })
+                .collect(),
+        }
+    }
+
+    /// Reassigns the `rank` of the rows currently present (eg. after
+    /// filtering the standings down to a few handles) from 1, ordering rows
+    /// by their [`normalized_scores`](Self::normalized_scores).
+    ///
+    /// Rows with equal scores share a rank, and the following rank skips
+    /// accordingly (eg. 1, 1, 3). Rows are also sorted into their new order.
+    pub fn recompute_ranks(&mut self) {
+        let scores = self.normalized_scores();
+        let mut ranked: Vec<(CFNormalizedScore, CFRanklistRow)> =
+            scores.into_iter().zip(self.rows.drain(..)).collect();
+        ranked.sort_by(|(a, _), (b, _)| {
+            b.partial_cmp(a).unwrap_or(Ordering::Equal)
+        });
+        let mut previous: Option<CFNormalizedScore> = None;
+        for (i, (score, mut row)) in ranked.into_iter().enumerate() {
+            row.rank = match (previous, self.rows.last()) {
+                (Some(p), Some(last)) if p == score => last.rank,
+                _ => i as i64 + 1,
+            };
+            previous = Some(score);
+            self.rows.push(row);
+        }
+    }
+}
+
+/// Clones the items of `items` whose position in `keep` is `true`.
+fn project_by<T: Clone>(items: &[T], keep: &[bool]) -> Vec<T> {
+    items
+        .iter()
+        .zip(keep)
+        .filter(|(_, &k)| k)
+        .map(|(item, _)| item.clone())
+        .collect()
+}
+
+/// Contest performance of a single party, as returned by
+/// [`CFContestStandings::scorecard_for`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct CFScorecard {
+    pub rank: i64,
+    pub points: f64,
+    pub penalty: i64,
+    /// Result of each problem of the contest, paired with its index (eg.
+    /// `"A"`), in the order of the standings' problems.
+    pub problems: Vec<(String, CFProblemResult)>,
+}
+
+/// Comparable score of a standings row, as returned by
+/// [`CFContestStandings::normalized_scores`].
+///
+/// Scores are ordered so that a higher `score` is greater and, for equal
+/// `score`, a lower `penalty` is greater.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CFNormalizedScore {
+    pub score: f64,
+    pub penalty: i64,
+}
+
+impl PartialOrd for CFNormalizedScore {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        match self.score.partial_cmp(&other.score)? {
+            Ordering::Equal => Some(other.penalty.cmp(&self.penalty)),
+            ord => Some(ord),
+        }
+    }
+}
+
 /// Struct representing a Codeforces
 /// [contest](https://codeforces.com/apiHelp/objects#Contest).
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct CFContest {
     pub id: i64,
     pub name: String,
@@ -344,8 +1031,193 @@ impl fmt::Display for CFContest {
     }
 }
 
+impl CFContest {
+    /// Returns `true` if the contest has not started yet (phase
+    /// [`CFContestPhase::Before`]) and is scheduled to start within
+    /// `within_seconds` of the UNIX time `now`.
+    ///
+    /// Contests with an unknown start time are never considered upcoming.
+    pub fn starts_within(&self, now: i64, within_seconds: i64) -> bool {
+        match self.start_time_seconds {
+            Some(start) => {
+                self.phase == CFContestPhase::Before
+                    && start >= now
+                    && start - now <= within_seconds
+            }
+            None => false,
+        }
+    }
+
+    /// Returns the time left until the contest starts, measured from the UNIX
+    /// time `now`, eg. for announcing "contest starts in 1 hour".
+    ///
+    /// Returns `None` if the contest has already started (by its phase or its
+    /// start time) or its start time is unknown.
+    pub fn time_until_start(&self, now: i64) -> Option<Duration> {
+        match self.start_time_seconds {
+            Some(start)
+                if self.phase == CFContestPhase::Before && start > now =>
+            {
+                Some(Duration::from_secs((start - now) as u64))
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the contest's `kind` parsed as a [`CFContestKind`], or `None`
+    /// if it is not set (which is the case for all non-gym contests).
+    pub fn kind_enum(&self) -> Option<CFContestKind> {
+        self.kind.as_deref().and_then(|k| k.parse().ok())
+    }
+
+    /// Returns the ICPC-specific fields of the contest (`icpc_region`,
+    /// `country`, `city` and `season`) bundled together, or `None` if none of
+    /// them are set. Fields are trimmed, and blank fields are treated as
+    /// unset.
+    pub fn icpc_metadata(&self) -> Option<CFIcpcMeta> {
+        let normalize = |s: &Option<String>| {
+            s.as_deref()
+                .map(str::trim)
+                .filter(|s| !s.is_empty())
+                .map(str::to_string)
+        };
+        let meta = CFIcpcMeta {
+            region: normalize(&self.icpc_region),
+            country: normalize(&self.country),
+            city: normalize(&self.city),
+            season: normalize(&self.season),
+        };
+        if meta == CFIcpcMeta::default() {
+            None
+        } else {
+            Some(meta)
+        }
+    }
+}
+
+/// Counts `contests` by duration, bucketed into whole hours: the key `2`
+/// counts contests lasting at least 2 but less than 3 hours.
+pub fn contest_duration_histogram(
+    contests: &[CFContest],
+) -> BTreeMap<i64, usize> {
+    let mut histogram = BTreeMap::new();
+    for contest in contests {
+        *histogram
+            .entry(contest.duration_seconds / 3600)
+            .or_insert(0) += 1;
+    }
+    histogram
+}
+
+/// ICPC-specific details of a contest, as returned by
+/// [`CFContest::icpc_metadata`].
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct CFIcpcMeta {
+    pub region: Option<String>,
+    pub country: Option<String>,
+    pub city: Option<String>,
+    pub season: Option<String>,
+}
+
+/// Kind of a (gym) contest, parsed from [`CFContest::kind`] (eg.
+/// `"Training Camp Contest"`).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum CFContestKind {
+    OfficialIcpc,
+    OfficialSchool,
+    Opencup,
+    SchoolUniversityChampionship,
+    TrainingCamp,
+    OfficialInternationalPersonal,
+    Training,
+    /// Any kind not (yet) known to this crate, preserved as returned.
+    Other(String),
+}
+
+impl std::str::FromStr for CFContestKind {
+    type Err = std::convert::Infallible;
+
+    /// Parses a contest kind as written by Codeforces, ignoring case and
+    /// surrounding whitespace. Unknown kinds are parsed as `Other`, so this
+    /// never fails.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s.trim().to_lowercase().as_str() {
+            "official icpc contest" => CFContestKind::OfficialIcpc,
+            "official school contest" => CFContestKind::OfficialSchool,
+            "opencup contest" => CFContestKind::Opencup,
+            "school/university/city/region championship" => {
+                CFContestKind::SchoolUniversityChampionship
+            }
+            "training camp contest" => CFContestKind::TrainingCamp,
+            "official international personal contest" => {
+                CFContestKind::OfficialInternationalPersonal
+            }
+            "training contest" => CFContestKind::Training,
+            _ => CFContestKind::Other(s.to_string()),
+        })
+    }
+}
+
+/// Family of a programming language, parsed from
+/// [`CFSubmission::programming_language`] (eg. `"GNU C++17"` and
+/// `"Clang++20 Diagnostics"` are both `Cpp`).
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum CFLanguageFamily {
+    C,
+    Cpp,
+    CSharp,
+    Go,
+    Haskell,
+    Java,
+    JavaScript,
+    Kotlin,
+    Pascal,
+    Python,
+    Rust,
+    /// Any language not (yet) known to this crate, preserved as returned.
+    Other(String),
+}
+
+impl std::str::FromStr for CFLanguageFamily {
+    type Err = std::convert::Infallible;
+
+    /// Parses the family of a language as written by Codeforces, ignoring
+    /// case and compiler versions. Unknown languages are parsed as `Other`,
+    /// so this never fails.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let name = s.trim().to_lowercase();
+        let words: Vec<&str> = name.split_whitespace().collect();
+        let has_word = |f: &dyn Fn(&str) -> bool| words.iter().any(|w| f(w));
+        Ok(if has_word(&|w| w.starts_with("pascal") || w == "delphi") {
+            CFLanguageFamily::Pascal
+        } else if name.contains("++") {
+            CFLanguageFamily::Cpp
+        } else if name.contains("c#") || name.contains(".net") {
+            CFLanguageFamily::CSharp
+        } else if name.starts_with("python") || name.starts_with("pypy") {
+            CFLanguageFamily::Python
+        } else if has_word(&|w| w == "javascript" || w == "node.js") {
+            CFLanguageFamily::JavaScript
+        } else if has_word(&|w| w == "java") {
+            CFLanguageFamily::Java
+        } else if has_word(&|w| w == "kotlin") {
+            CFLanguageFamily::Kotlin
+        } else if has_word(&|w| w == "rust") {
+            CFLanguageFamily::Rust
+        } else if has_word(&|w| w == "go") {
+            CFLanguageFamily::Go
+        } else if has_word(&|w| w == "haskell") {
+            CFLanguageFamily::Haskell
+        } else if has_word(&|w| w == "c" || w.starts_with("c1")) {
+            CFLanguageFamily::C
+        } else {
+            CFLanguageFamily::Other(s.to_string())
+        })
+    }
+}
+
 /// Participant type returned by Codeforces API (eg. Contestant, Virtual).
-#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum CFParticipantType {
     Contestant,
@@ -353,6 +1225,9 @@ pub enum CFParticipantType {
     Virtual,
     Manager,
     OutOfCompetition,
+    /// Any value not (yet) known to this crate, preserved as returned.
+    #[serde(untagged)]
+    Unknown(String),
 }
 
 #[cfg(feature = "serde_yaml")]
@@ -366,10 +1241,26 @@ impl fmt::Display for CFParticipantType {
     }
 }
 
+impl CFParticipantType {
+    /// Returns all known participant types, excluding `Unknown`. Useful for
+    /// building filters or exhaustive tests.
+    pub fn all_variants() -> &'static [CFParticipantType] {
+        const ALL: &[CFParticipantType] = &[
+            CFParticipantType::Contestant,
+            CFParticipantType::Practice,
+            CFParticipantType::Virtual,
+            CFParticipantType::Manager,
+            CFParticipantType::OutOfCompetition,
+        ];
+        ALL
+    }
+}
+
 /// Struct representing a Codeforces
 /// [party](https://codeforces.com/apiHelp/objects#Party).
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct CFParty {
     pub contest_id: Option<i64>,
     pub members: Vec<CFMember>,
@@ -392,10 +1283,76 @@ impl fmt::Display for CFParty {
     }
 }
 
+/// Kind of a [`CFParty`], as returned by [`CFParty::kind`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CFPartyKind {
+    /// A single participant.
+    Solo,
+    /// A team, ie. a party with a `team_id`.
+    Team,
+    /// A ghost participant, ie. one imported from an onsite or external
+    /// contest, whether or not it is a team.
+    Ghost,
+}
+
+/// Identity of a [`CFParty`] within one set of standings, as returned by
+/// `CFParty::key()`: its participant type, team id and (for non-teams) sorted
+/// member handles.
+type PartyKey = (CFParticipantType, Option<i64>, Vec<String>);
+
+impl CFParty {
+    /// Identifies the party by its participant type together with its
+    /// `team_id` if it is a team, or its sorted member handles otherwise.
+    fn key(&self) -> PartyKey {
+        let members = match self.team_id {
+            Some(_) => vec![],
+            None => {
+                let mut handles: Vec<String> =
+                    self.members.iter().map(|m| m.handle.clone()).collect();
+                handles.sort_unstable();
+                handles
+            }
+        };
+        (self.participant_type.clone(), self.team_id, members)
+    }
+
+    /// Classifies the party as a ghost, a team or a solo participant, in that
+    /// order of precedence.
+    pub fn kind(&self) -> CFPartyKind {
+        if self.ghost {
+            CFPartyKind::Ghost
+        } else if self.team_id.is_some() {
+            CFPartyKind::Team
+        } else {
+            CFPartyKind::Solo
+        }
+    }
+
+    /// Returns a human readable name for the party: the team name if there
+    /// is one, otherwise the member handles joined by `", "`. Ghost parties
+    /// have a ` (ghost)` suffix appended.
+    pub fn display_name(&self) -> String {
+        let mut name = match self.team_name {
+            Some(ref team_name) => team_name.clone(),
+            None => self
+                .members
+                .iter()
+                .map(|m| m.handle.as_str())
+                .collect::<Vec<_>>()
+                .join(", "),
+        };
+        if self.ghost {
+            name += " (ghost)";
+        }
+        name
+    }
+}
+
 /// Struct representing a Codeforces
 /// [member](https://codeforces.com/apiHelp/objects#Member).
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct CFMember {
     pub handle: String,
 }
@@ -412,11 +1369,14 @@ impl fmt::Display for CFMember {
 }
 
 /// Problem type returned by Codeforces API (Programming, Question).
-#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum CFProblemType {
     Programming,
     Question,
+    /// Any value not (yet) known to this crate, preserved as returned.
+    #[serde(untagged)]
+    Unknown(String),
 }
 
 #[cfg(feature = "serde_yaml")]
@@ -430,10 +1390,21 @@ impl fmt::Display for CFProblemType {
     }
 }
 
+impl CFProblemType {
+    /// Returns all known problem types, excluding `Unknown`. Useful for
+    /// building filters or exhaustive tests.
+    pub fn all_variants() -> &'static [CFProblemType] {
+        const ALL: &[CFProblemType] =
+            &[CFProblemType::Programming, CFProblemType::Question];
+        ALL
+    }
+}
+
 /// Struct representing a Codeforces
 /// [problem](https://codeforces.com/apiHelp/objects#Problem).
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct CFProblem {
     pub contest_id: Option<i64>,
     pub problemset_name: Option<String>,
@@ -441,6 +1412,7 @@ pub struct CFProblem {
     pub name: String,
     #[serde(rename = "type")]
     pub problem_type: CFProblemType,
+    #[serde(default, deserialize_with = "deserialize_optional_points")]
     pub points: Option<f64>,
     pub rating: Option<i64>,
     pub tags: Vec<String>,
@@ -459,10 +1431,92 @@ impl fmt::Display for CFProblem {
     }
 }
 
+impl CFProblem {
+    /// Splits the problem's `index` into its base letter and numeric
+    /// subindex, if any, eg. `"A1"` into `('A', Some(1))` and `"C"` into
+    /// `('C', None)`. Useful for grouping divided problems (`A1`, `A2`) under
+    /// their base letter.
+    ///
+    /// Returns `None` if the problem has no index or it is not of that form
+    /// (eg. the numeric indices of the `acmsguru` problemset).
+    pub fn parse_index(&self) -> Option<(char, Option<u32>)> {
+        let index = self.index.as_deref()?;
+        let mut chars = index.chars();
+        let letter = chars.next().filter(|c| c.is_ascii_alphabetic())?;
+        let rest = chars.as_str();
+        if rest.is_empty() {
+            Some((letter, None))
+        } else if rest.bytes().all(|b| b.is_ascii_digit()) {
+            Some((letter, Some(rest.parse().ok()?)))
+        } else {
+            None
+        }
+    }
+
+    /// Returns the color of the rating band of the problem's difficulty, as
+    /// displayed by Codeforces, or `None` if the problem is unrated.
+    pub fn difficulty_color(&self) -> Option<CFRankColor> {
+        self.rating.map(CFRankColor::from_rating)
+    }
+
+    /// Construct a minimal problem with only `contest_id` and `index` set,
+    /// which is enough to fetch its testcases with
+    /// [`fetch_testcases`](CFProblem::fetch_testcases). The remaining fields
+    /// are left empty (and `problem_type` is set to `Programming`).
+    pub fn from_contest_index(contest_id: i64, index: &str) -> CFProblem {
+        CFProblem {
+            contest_id: Some(contest_id),
+            problemset_name: None,
+            index: Some(index.to_string()),
+            name: String::new(),
+            problem_type: CFProblemType::Programming,
+            points: None,
+            rating: None,
+            tags: vec![],
+            input_testcases: None,
+        }
+    }
+
+    /// Returns `true` if the problem is tagged with `tag`, ignoring case.
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.iter().any(|t| t.eq_ignore_ascii_case(tag))
+    }
+
+    /// Returns `true` if the problem is tagged with every tag in `tags`,
+    /// ignoring case.
+    pub fn has_all_tags(&self, tags: &[&str]) -> bool {
+        tags.iter().all(|t| self.has_tag(t))
+    }
+
+    /// Returns `true` if `self` and `other` refer to the same problem,
+    /// ignoring fields such as `tags` or `input_testcases` which may differ
+    /// between copies.
+    ///
+    /// Problems are compared by `(contest_id, index)` when both problems have
+    /// them, falling back to `(problemset_name, name)` otherwise.
+    pub fn same_problem(&self, other: &CFProblem) -> bool {
+        match (self.contest_id, &self.index, other.contest_id, &other.index) {
+            (Some(c1), Some(i1), Some(c2), Some(i2)) => c1 == c2 && i1 == i2,
+            _ => {
+                self.problemset_name == other.problemset_name
+                    && self.name == other.name
+            }
+        }
+    }
+}
+
+/// Equivalent to [`CFProblem::from_contest_index`].
+impl From<(i64, &str)> for CFProblem {
+    fn from((contest_id, index): (i64, &str)) -> Self {
+        CFProblem::from_contest_index(contest_id, index)
+    }
+}
+
 /// Struct representing a Codeforces problem
 /// [statistics](https://codeforces.com/apiHelp/objects#ProblemStatistics).
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct CFProblemStatistics {
     pub contest_id: Option<i64>,
     pub index: Option<String>,
@@ -480,11 +1534,44 @@ impl fmt::Display for CFProblemStatistics {
     }
 }
 
+/// Returns the difficulty rating of `problem`, or a coarse estimate derived
+/// from `statistics.solved_count` when the problem is unrated.
+///
+/// The estimate is purely heuristic: the fewer people have solved a problem,
+/// the harder it is assumed to be, in buckets ranging from 800 (solved by at
+/// least 20000 users) to 3500 (solved by fewer than 100 users). Returns
+/// `None` if `statistics` does not belong to `problem`.
+pub fn estimated_difficulty(
+    problem: &CFProblem,
+    statistics: &CFProblemStatistics,
+) -> Option<i64> {
+    if problem.contest_id != statistics.contest_id
+        || problem.index != statistics.index
+    {
+        return None;
+    }
+    if problem.rating.is_some() {
+        return problem.rating;
+    }
+    let estimate = match statistics.solved_count {
+        n if n >= 20000 => 800,
+        n if n >= 10000 => 1200,
+        n if n >= 5000 => 1500,
+        n if n >= 2000 => 1800,
+        n if n >= 1000 => 2100,
+        n if n >= 500 => 2400,
+        n if n >= 100 => 2800,
+        _ => 3500,
+    };
+    Some(estimate)
+}
+
 /// Struct representing the object returned by a
 /// [`problemset.problems`](super::requests::CFProblemsetCommand::Problems)
 /// request.
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct CFProblemset {
     pub problems: Vec<CFProblem>,
     pub problem_statistics: Vec<CFProblemStatistics>,
@@ -501,8 +1588,83 @@ impl fmt::Display for CFProblemset {
     }
 }
 
+impl CFProblemset {
+    /// Returns the distinct tags of all problems in the problemset, sorted
+    /// alphabetically.
+    pub fn distinct_tags(&self) -> Vec<String> {
+        let mut tags: Vec<String> = self
+            .problems
+            .iter()
+            .flat_map(|p| p.tags.iter().cloned())
+            .collect();
+        tags.sort_unstable();
+        tags.dedup();
+        tags
+    }
+
+    /// Picks a random problem which carries all of `tags` and whose rating
+    /// lies within `min_rating..=max_rating`, or `None` if no problem
+    /// matches. Unrated problems never match.
+    ///
+    /// Passing a `seed` makes the pick reproducible for the same problemset,
+    /// using `rand`'s [`StdRng`](rand::rngs::StdRng). Its algorithm may change
+    /// between versions of `rand`, so a seed is only guaranteed to give the
+    /// same pick with the same version of this crate's dependencies. Without
+    /// a seed, a new random pick is made on every call.
+    pub fn random_problem(
+        &self,
+        tags: &[&str],
+        min_rating: i64,
+        max_rating: i64,
+        seed: Option<u64>,
+    ) -> Option<&CFProblem> {
+        use rand::{RngCore, SeedableRng};
+
+        let candidates: Vec<&CFProblem> = self
+            .problems
+            .iter()
+            .filter(|p| {
+                p.rating.is_some_and(|r| min_rating <= r && r <= max_rating)
+                    && tags.iter().all(|t| p.tags.iter().any(|pt| pt == t))
+            })
+            .collect();
+        if candidates.is_empty() {
+            return None;
+        }
+        let random = match seed {
+            Some(seed) => rand::rngs::StdRng::seed_from_u64(seed).next_u64(),
+            None => rand::thread_rng().next_u64(),
+        };
+        Some(candidates[(random % candidates.len() as u64) as usize])
+    }
+
+    /// Returns how many problems in the problemset carry each tag.
+    pub fn tag_frequencies(&self) -> HashMap<String, usize> {
+        let mut frequencies = HashMap::new();
+        for tag in self.problems.iter().flat_map(|p| &p.tags) {
+            *frequencies.entry(tag.clone()).or_insert(0) += 1;
+        }
+        frequencies
+    }
+
+    /// Returns the problems sorted by contest id and then by index.
+    ///
+    /// Purely numeric indices (as used by eg. the `acmsguru` problemset) are
+    /// sorted numerically and placed before any other indices, which are
+    /// sorted lexicographically. This means `"20"` comes before `"101"`.
+    pub fn sorted_by_index(&self) -> Vec<&CFProblem> {
+        let mut problems: Vec<&CFProblem> = self.problems.iter().collect();
+        problems.sort_by_key(|p| {
+            let index = p.index.as_deref().unwrap_or("");
+            let numeric = index.parse::<u64>().ok();
+            (p.contest_id, numeric.is_none(), numeric, index)
+        });
+        problems
+    }
+}
+
 /// Submission verdict returned by Codeforces API (eg. Ok, CompilationError).
-#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum CFSubmissionVerdict {
     Failed,
@@ -522,6 +1684,9 @@ pub enum CFSubmissionVerdict {
     Skipped,
     Testing,
     Rejected,
+    /// Any value not (yet) known to this crate, preserved as returned.
+    #[serde(untagged)]
+    Unknown(String),
 }
 
 #[cfg(feature = "serde_yaml")]
@@ -535,8 +1700,35 @@ impl fmt::Display for CFSubmissionVerdict {
     }
 }
 
+impl CFSubmissionVerdict {
+    /// Returns all known submission verdicts, excluding `Unknown`. Useful for
+    /// building filters or exhaustive tests.
+    pub fn all_variants() -> &'static [CFSubmissionVerdict] {
+        const ALL: &[CFSubmissionVerdict] = &[
+            CFSubmissionVerdict::Failed,
+            CFSubmissionVerdict::Ok,
+            CFSubmissionVerdict::Partial,
+            CFSubmissionVerdict::CompilationError,
+            CFSubmissionVerdict::RuntimeError,
+            CFSubmissionVerdict::WrongAnswer,
+            CFSubmissionVerdict::PresentationError,
+            CFSubmissionVerdict::TimeLimitExceeded,
+            CFSubmissionVerdict::MemoryLimitExceeded,
+            CFSubmissionVerdict::IdlenessLimitExceeded,
+            CFSubmissionVerdict::SecurityViolated,
+            CFSubmissionVerdict::Crashed,
+            CFSubmissionVerdict::InputPreparationCrashed,
+            CFSubmissionVerdict::Challenged,
+            CFSubmissionVerdict::Skipped,
+            CFSubmissionVerdict::Testing,
+            CFSubmissionVerdict::Rejected,
+        ];
+        ALL
+    }
+}
+
 /// Testset returned by Codeforces API (eg. Pretests, TestSet1).
-#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum CFTestset {
     Samples,
@@ -563,6 +1755,9 @@ pub enum CFTestset {
     TestSet9,
     #[serde(rename = "TESTS10")]
     TestSet10,
+    /// Any value not (yet) known to this crate, preserved as returned.
+    #[serde(untagged)]
+    Unknown(String),
 }
 
 #[cfg(feature = "serde_yaml")]
@@ -580,6 +1775,7 @@ impl fmt::Display for CFTestset {
 /// [submission](https://codeforces.com/apiHelp/objects#Submission).
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct CFSubmission {
     pub id: i64,
     pub contest_id: Option<i64>,
@@ -593,6 +1789,7 @@ pub struct CFSubmission {
     pub passed_test_count: i64,
     pub time_consumed_millis: i64,
     pub memory_consumed_bytes: i64,
+    #[serde(default, deserialize_with = "deserialize_optional_points")]
     pub points: Option<f64>,
 }
 
@@ -607,8 +1804,210 @@ impl fmt::Display for CFSubmission {
     }
 }
 
+/// Value used by Codeforces for `relative_time_seconds` of submissions which
+/// were not made during a contest (eg. practice submissions), `2^31 - 1`.
+pub const CF_RELATIVE_TIME_SENTINEL: i64 = 2_147_483_647;
+
+/// Resource usage of a submission as fractions of the problem's limits, as
+/// returned by [`CFSubmission::limit_usage`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CFLimitUsage {
+    /// `time_consumed_millis` divided by the time limit.
+    pub time: f64,
+    /// `memory_consumed_bytes` divided by the memory limit.
+    pub memory: f64,
+}
+
+/// Summary of how a submission scored, as returned by
+/// [`CFSubmission::score_summary`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct CFScoreSummary {
+    /// `None` while the submission is still queued.
+    pub verdict: Option<CFSubmissionVerdict>,
+    /// `None` unless the contest awards points per submission (eg. IOI or
+    /// partial scoring contests).
+    pub points: Option<f64>,
+    pub testset: CFTestset,
+    pub passed_test_count: i64,
+    /// Fraction of the tests of the testset which passed. The API does not
+    /// return the number of tests in a testset, so this is only known for
+    /// accepted submissions (`1.0`) and is `None` otherwise.
+    pub pass_ratio: Option<f64>,
+}
+
+impl CFSubmission {
+    /// Returns the submission's `programming_language` parsed as a
+    /// [`CFLanguageFamily`].
+    pub fn language_family(&self) -> CFLanguageFamily {
+        match self.programming_language.parse() {
+            Ok(family) => family,
+            Err(e) => match e {},
+        }
+    }
+
+    /// Bundles the scoring-related fields of the submission (verdict, points,
+    /// testset and passed tests) together with a pass ratio where it can be
+    /// derived. See [`CFScoreSummary`] for which fields may be unavailable.
+    pub fn score_summary(&self) -> CFScoreSummary {
+        CFScoreSummary {
+            verdict: self.verdict.clone(),
+            points: self.points,
+            testset: self.testset.clone(),
+            passed_test_count: self.passed_test_count,
+            pass_ratio: match self.verdict {
+                Some(CFSubmissionVerdict::Ok) => Some(1.0),
+                _ => None,
+            },
+        }
+    }
+
+    /// Returns how close the submission came to the given time and memory
+    /// limits of its problem, eg. a `time` of `0.9` means 90% of the time
+    /// limit was used. The API does not return the limits of a problem, so
+    /// they have to be given here.
+    pub fn limit_usage(
+        &self,
+        time_limit_millis: i64,
+        memory_limit_bytes: i64,
+    ) -> CFLimitUsage {
+        CFLimitUsage {
+            time: self.time_consumed_millis as f64 / time_limit_millis as f64,
+            memory: self.memory_consumed_bytes as f64
+                / memory_limit_bytes as f64,
+        }
+    }
+
+    /// Returns `true` if the submission was made during the contest, ie. its
+    /// `relative_time_seconds` is known and is not
+    /// [`CF_RELATIVE_TIME_SENTINEL`].
+    pub fn is_in_contest(&self) -> bool {
+        match self.relative_time_seconds {
+            Some(t) => t != CF_RELATIVE_TIME_SENTINEL,
+            None => false,
+        }
+    }
+
+    /// Finds the submission's problem in a fetched `problemset`, matched by
+    /// `(contest_id, index)`. The problemset version of a problem usually
+    /// has `rating` and `tags` which the one embedded in a submission lacks.
+    ///
+    /// Returns `None` if the submission's problem has no contest id or index,
+    /// or if it is not in the problemset.
+    pub fn problem_in<'a>(
+        &self,
+        problemset: &'a CFProblemset,
+    ) -> Option<&'a CFProblem> {
+        let contest_id = self.problem.contest_id?;
+        let index = self.problem.index.as_deref()?;
+        problemset.problems.iter().find(|p| {
+            p.contest_id == Some(contest_id)
+                && p.index.as_deref() == Some(index)
+        })
+    }
+}
+
+/// Returns the best points achieved on each problem across `submissions`,
+/// keyed by `(contest_id, problem_index)`.
+///
+/// Intended for IOI-style contests where submissions can score partial
+/// points. Submissions without points, a contest id or a problem index are
+/// ignored.
+pub fn best_points_per_problem(
+    submissions: &[CFSubmission],
+) -> HashMap<(i64, String), f64> {
+    let mut best: HashMap<(i64, String), f64> = HashMap::new();
+    for submission in submissions {
+        let contest_id =
+            submission.contest_id.or(submission.problem.contest_id);
+        if let (Some(contest_id), Some(index), Some(points)) = (
+            contest_id,
+            submission.problem.index.as_ref(),
+            submission.points,
+        ) {
+            let entry =
+                best.entry((contest_id, index.clone())).or_insert(points);
+            if points > *entry {
+                *entry = points;
+            }
+        }
+    }
+    best
+}
+
+/// Groups `submissions` by the participant type of their author, eg. to
+/// separate contest submissions (`Contestant`) from practice or virtual ones.
+/// Submissions keep their original order within each group.
+pub fn partition_by_participant(
+    submissions: &[CFSubmission],
+) -> HashMap<CFParticipantType, Vec<&CFSubmission>> {
+    let mut groups: HashMap<CFParticipantType, Vec<&CFSubmission>> =
+        HashMap::new();
+    for submission in submissions {
+        groups
+            .entry(submission.author.participant_type.clone())
+            .or_default()
+            .push(submission);
+    }
+    groups
+}
+
+/// Returns the distinct `programming_language`s of `submissions` (eg.
+/// `"GNU C++17"`), sorted alphabetically.
+pub fn languages_used(submissions: &[CFSubmission]) -> BTreeSet<String> {
+    submissions
+        .iter()
+        .map(|s| s.programming_language.clone())
+        .collect()
+}
+
+/// Returns the distinct [`CFLanguageFamily`]s of `submissions`, so that eg.
+/// `"GNU C++17"` and `"GNU C++20"` count as one language.
+pub fn language_families(
+    submissions: &[CFSubmission],
+) -> BTreeSet<CFLanguageFamily> {
+    submissions
+        .iter()
+        .map(CFSubmission::language_family)
+        .collect()
+}
+
+/// Returns the earliest `creation_time_seconds` among the accepted (`Ok`)
+/// submissions in `submissions` to problem `index` of contest `contest_id`,
+/// or `None` if there are none.
+pub fn first_accepted_time(
+    submissions: &[CFSubmission],
+    contest_id: i64,
+    index: &str,
+) -> Option<i64> {
+    submissions
+        .iter()
+        .filter(|s| s.verdict == Some(CFSubmissionVerdict::Ok))
+        .filter(|s| {
+            s.contest_id.or(s.problem.contest_id) == Some(contest_id)
+                && s.problem.index.as_deref() == Some(index)
+        })
+        .map(|s| s.creation_time_seconds)
+        .min()
+}
+
+/// Returns the distinct `(contest_id, index)` pairs of the problems with at
+/// least one accepted (`Ok`) submission in `submissions`. Submissions to
+/// problems without a contest id (eg. `acmsguru`) are ignored.
+pub fn solved_problems(submissions: &[CFSubmission]) -> HashSet<(i64, String)> {
+    submissions
+        .iter()
+        .filter(|s| s.verdict == Some(CFSubmissionVerdict::Ok))
+        .filter_map(|s| {
+            Some((
+                s.contest_id.or(s.problem.contest_id)?,
+                s.problem.index.clone()?,
+            ))
+        })
+        .collect()
+}
+
 /// Hack verdict returned by Codeforces API (eg. HackSuccessful, Testing).
-#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum CFHackVerdict {
     HackSuccessful,
@@ -619,6 +2018,9 @@ pub enum CFHackVerdict {
     Ignored,
     Testing,
     Other,
+    /// Any value not (yet) known to this crate, preserved as returned.
+    #[serde(untagged)]
+    Unknown(String),
 }
 
 #[cfg(feature = "serde_yaml")]
@@ -632,9 +2034,28 @@ impl fmt::Display for CFHackVerdict {
     }
 }
 
+impl CFHackVerdict {
+    /// Returns all known hack verdicts, excluding `Unknown`. Useful for
+    /// building filters or exhaustive tests.
+    pub fn all_variants() -> &'static [CFHackVerdict] {
+        const ALL: &[CFHackVerdict] = &[
+            CFHackVerdict::HackSuccessful,
+            CFHackVerdict::HackUnsuccessful,
+            CFHackVerdict::InvalidInput,
+            CFHackVerdict::GeneratorIncompilable,
+            CFHackVerdict::GeneratorCrashed,
+            CFHackVerdict::Ignored,
+            CFHackVerdict::Testing,
+            CFHackVerdict::Other,
+        ];
+        ALL
+    }
+}
+
 /// Struct representing a Codeforces judge protocol for hacks.
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct CFJudgeProtocol {
     pub manual: String,
     pub protocol: String,
@@ -656,6 +2077,7 @@ impl fmt::Display for CFJudgeProtocol {
 /// [hack](https://codeforces.com/apiHelp/objects#Hack).
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct CFHack {
     pub id: i64,
     pub creation_time_seconds: i64,
@@ -678,13 +2100,45 @@ impl fmt::Display for CFHack {
     }
 }
 
+/// Counts the successful hacks made by each hacker, keyed by the
+/// [`display_name`](CFParty::display_name) of the hacking party.
+pub fn hacks_by_hacker(hacks: &[CFHack]) -> HashMap<String, usize> {
+    count_successful_hacks(hacks, |h| &h.hacker)
+}
+
+/// Counts the successful hacks made against each defender, keyed by the
+/// [`display_name`](CFParty::display_name) of the defending party.
+pub fn hacks_against(hacks: &[CFHack]) -> HashMap<String, usize> {
+    count_successful_hacks(hacks, |h| &h.defender)
+}
+
+/// Tallies successful hacks by the display name of the party selected by
+/// `party`.
+fn count_successful_hacks<F>(
+    hacks: &[CFHack],
+    party: F,
+) -> HashMap<String, usize>
+where
+    F: Fn(&CFHack) -> &CFParty,
+{
+    let mut counts = HashMap::new();
+    for hack in hacks {
+        if hack.verdict == Some(CFHackVerdict::HackSuccessful) {
+            *counts.entry(party(hack).display_name()).or_insert(0) += 1;
+        }
+    }
+    counts
+}
+
 /// Struct representing a Codeforces
 /// [ranklist row](https://codeforces.com/apiHelp/objects#RanklistRow).
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct CFRanklistRow {
     pub party: CFParty,
     pub rank: i64,
+    #[serde(deserialize_with = "deserialize_points")]
     pub points: f64,
     pub penalty: i64,
     pub successful_hack_count: i64,
@@ -705,11 +2159,14 @@ impl fmt::Display for CFRanklistRow {
 }
 
 /// Problem result type returned by Codeforces API (Preliminary, Final).
-#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum CFProblemResultType {
     Preliminary,
     Final,
+    /// Any value not (yet) known to this crate, preserved as returned.
+    #[serde(untagged)]
+    Unknown(String),
 }
 
 #[cfg(feature = "serde_yaml")]
@@ -723,11 +2180,23 @@ impl fmt::Display for CFProblemResultType {
     }
 }
 
+impl CFProblemResultType {
+    /// Returns all known problem result types, excluding `Unknown`. Useful for
+    /// building filters or exhaustive tests.
+    pub fn all_variants() -> &'static [CFProblemResultType] {
+        const ALL: &[CFProblemResultType] =
+            &[CFProblemResultType::Preliminary, CFProblemResultType::Final];
+        ALL
+    }
+}
+
 /// Struct representing a Codeforces
 /// [problem result](https://codeforces.com/apiHelp/objects#ProblemResult).
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct CFProblemResult {
+    #[serde(deserialize_with = "deserialize_points")]
     pub points: f64,
     pub penalty: Option<i64>,
     pub rejected_attempt_count: i64,
@@ -746,3 +2215,43 @@ impl fmt::Display for CFProblemResult {
         }
     }
 }
+
+/// Points value as it may appear in JSON: either a number (integer or float)
+/// or a String containing a number.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum LenientPoints {
+    Number(f64),
+    String(String),
+}
+
+impl LenientPoints {
+    fn into_f64<E: serde::de::Error>(self) -> Result<f64, E> {
+        match self {
+            LenientPoints::Number(n) => Ok(n),
+            LenientPoints::String(s) => s.trim().parse().map_err(E::custom),
+        }
+    }
+}
+
+/// Deserializes a `points` field which may be an integer, a float or a
+/// numeric String.
+fn deserialize_points<'de, D>(deserializer: D) -> Result<f64, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    LenientPoints::deserialize(deserializer)?.into_f64()
+}
+
+/// Analogous to `deserialize_points()`, but for optional `points` fields.
+fn deserialize_optional_points<'de, D>(
+    deserializer: D,
+) -> Result<Option<f64>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    match Option::<LenientPoints>::deserialize(deserializer)? {
+        Some(p) => p.into_f64().map(Some),
+        None => Ok(None),
+    }
+}
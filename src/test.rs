@@ -114,7 +114,7 @@ fn test_fetch_testcase() {
     };
     match p.fetch_testcases() {
         Ok(v) => {
-            assert!(p.input_testcases.unwrap().len() > 0);
+            assert!(!p.input_testcases.unwrap().is_empty());
             println!("Received problem testcases successfully: {:?}", v);
         }
         Err(e) => {
@@ -122,3 +122,3010 @@ fn test_fetch_testcase() {
         }
     }
 }
+
+#[cfg(feature = "use_testcase_fetcher")]
+#[test]
+fn test_write_testcases_to_dir() {
+    let dir = std::env::temp_dir().join("codeforces_api_test_testcases");
+    let _ = std::fs::remove_dir_all(&dir);
+    let testcases = vec!["1\n2 3\n".to_string(), "4\n5 6\n".to_string()];
+    let paths = write_testcases_to_dir(&dir, "B", &testcases).unwrap();
+    assert_eq!(paths, vec![dir.join("B_1.txt"), dir.join("B_2.txt")]);
+    for (path, testcase) in paths.iter().zip(testcases.iter()) {
+        assert_eq!(&std::fs::read_to_string(path).unwrap(), testcase);
+    }
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[cfg(feature = "use_testcase_fetcher")]
+#[test]
+fn test_client_download_testcases_to_dir() {
+    use crate::client::CFClient;
+
+    let page = "<html><body>\
+        <div class=\"input\"><pre>1\n2 3\n</pre></div>\
+        <div class=\"output\"><pre>5\n</pre></div>\
+        <div class=\"input\"><pre>4\n5 6\n</pre></div>\
+        </body></html>"
+        .to_string();
+    let (base_url, server) = serve_once("text/html", page);
+    let (k, s) = get_api_keys();
+    let client = CFClient::new(k, s)
+        .with_web_url(base_url.trim_end_matches("api/"))
+        .unwrap();
+    let dir = std::env::temp_dir().join("codeforces_api_test_download");
+    let _ = std::fs::remove_dir_all(&dir);
+    let problem = CFProblem::from_contest_index(1477, "B");
+    let paths = client.download_testcases_to_dir(&problem, &dir).unwrap();
+    assert_eq!(paths, vec![dir.join("B_1.txt"), dir.join("B_2.txt")]);
+    assert_eq!(std::fs::read_to_string(&paths[0]).unwrap(), "1\n2 3\n");
+    assert_eq!(std::fs::read_to_string(&paths[1]).unwrap(), "4\n5 6\n");
+    std::fs::remove_dir_all(&dir).unwrap();
+    let requests = server.join().unwrap();
+    assert!(requests[0].starts_with("GET /contest/1477/problem/B "));
+
+    // a problem without an index fails like `fetch_testcases` does, without
+    // sending a request.
+    let problem = CFProblem {
+        index: None,
+        ..problem
+    };
+    match client.download_testcases_to_dir(&problem, &dir) {
+        Err(Error::Testcases(s)) => {
+            assert_eq!(s, "problem.index field is required.")
+        }
+        r => panic!("expected a testcases error, got {:?}", r),
+    }
+    assert_eq!(client.stats().requests, 1);
+}
+
+#[test]
+fn test_error_is_retryable() {
+    let rate_limited = Error::CodeforcesApi("Call limit exceeded".to_string());
+    assert!(rate_limited.is_retryable());
+    let bad_auth =
+        Error::CodeforcesApi("apiKey: Incorrect API key".to_string());
+    assert!(!bad_auth.is_retryable());
+}
+
+fn test_contest(
+    id: i64,
+    phase: CFContestPhase,
+    start: Option<i64>,
+) -> CFContest {
+    CFContest {
+        id,
+        name: format!("Contest {}", id),
+        contest_type: CFContestType::Codeforces,
+        phase,
+        duration_seconds: 7200,
+        start_time_seconds: start,
+        relative_time_seconds: None,
+        prepared_by: None,
+        website_url: None,
+        description: None,
+        difficulty: None,
+        kind: None,
+        icpc_region: None,
+        country: None,
+        city: None,
+        season: None,
+    }
+}
+
+#[test]
+fn test_contest_starts_within() {
+    let now = 1_600_000_000;
+    let contests = [
+        test_contest(1, CFContestPhase::Before, Some(now + 3600)),
+        test_contest(2, CFContestPhase::Before, Some(now + 3 * 86400)),
+        test_contest(3, CFContestPhase::Before, None),
+        test_contest(4, CFContestPhase::Coding, Some(now - 60)),
+        test_contest(5, CFContestPhase::Before, Some(now + 7200)),
+    ];
+    let upcoming: Vec<i64> = contests
+        .iter()
+        .filter(|c| c.starts_within(now, 86400))
+        .map(|c| c.id)
+        .collect();
+    assert_eq!(upcoming, vec![1, 5]);
+}
+
+#[cfg(feature = "strict")]
+#[test]
+fn test_strict_rejects_unknown_fields() {
+    let known = r#"{"handle":"thud"}"#;
+    assert!(serde_json::from_str::<CFMember>(known).is_ok());
+    let unknown = r#"{"handle":"thud","someNewField":1}"#;
+    assert!(serde_json::from_str::<CFMember>(unknown).is_err());
+}
+
+fn test_party(
+    handles: &[&str],
+    team_name: Option<&str>,
+    ghost: bool,
+) -> CFParty {
+    CFParty {
+        contest_id: Some(1477),
+        members: handles
+            .iter()
+            .map(|h| CFMember {
+                handle: h.to_string(),
+            })
+            .collect(),
+        participant_type: CFParticipantType::Contestant,
+        team_id: team_name.map(|_| 1),
+        team_name: team_name.map(|s| s.to_string()),
+        ghost,
+        room: None,
+        start_time_seconds: None,
+    }
+}
+
+#[test]
+fn test_party_display_name() {
+    assert_eq!(test_party(&["thud"], None, false).display_name(), "thud");
+    assert_eq!(
+        test_party(&["a", "b"], Some("Team AB"), false).display_name(),
+        "Team AB"
+    );
+    assert_eq!(test_party(&["a", "b"], None, false).display_name(), "a, b");
+    assert_eq!(
+        test_party(&["thud"], None, true).display_name(),
+        "thud (ghost)"
+    );
+}
+
+#[test]
+fn test_standings_changed_rows() {
+    let party = |h: &str, participant_type| CFParty {
+        participant_type,
+        ..test_party(&[h], None, false)
+    };
+    let previous = test_standings(
+        CFContestType::Codeforces,
+        vec![
+            test_row(
+                party("thud", CFParticipantType::Contestant),
+                1,
+                0,
+                &[1.0],
+            ),
+            test_row(party("thud", CFParticipantType::Virtual), 2, 0, &[1.0]),
+            test_row(
+                party("other", CFParticipantType::Contestant),
+                3,
+                0,
+                &[0.0],
+            ),
+        ],
+    );
+    let current = test_standings(
+        CFContestType::Codeforces,
+        vec![
+            test_row(
+                party("thud", CFParticipantType::Contestant),
+                1,
+                0,
+                &[1.0],
+            ),
+            test_row(
+                party("other", CFParticipantType::Contestant),
+                2,
+                0,
+                &[1.0],
+            ),
+            test_row(party("thud", CFParticipantType::Virtual), 3, 0, &[1.0]),
+            test_row(party("new", CFParticipantType::Practice), 4, 0, &[0.0]),
+        ],
+    );
+    let changed: Vec<(String, CFParticipantType)> = current
+        .changed_rows(&previous)
+        .into_iter()
+        .map(|r| (r.party.display_name(), r.party.participant_type.clone()))
+        .collect();
+    assert_eq!(
+        changed,
+        vec![
+            ("other".to_string(), CFParticipantType::Contestant),
+            ("thud".to_string(), CFParticipantType::Virtual),
+            ("new".to_string(), CFParticipantType::Practice),
+        ]
+    );
+    assert!(current.changed_rows(&current).is_empty());
+}
+
+#[test]
+fn test_client_request_logger() {
+    use crate::client::CFClient;
+    use std::sync::{Arc, Mutex};
+
+    let body = format!(
+        r#"{{"status":"OK","result":[{}]}}"#,
+        serde_json::to_string(&test_user("thud", None)).unwrap()
+    );
+    let (base_url, server) = serve_once("application/json", body);
+    let (k, s) = get_api_keys();
+    let logged = Arc::new(Mutex::new(Vec::new()));
+    let sink = Arc::clone(&logged);
+    let client = CFClient::new(k, s)
+        .with_base_url(&base_url)
+        .unwrap()
+        .with_request_logger(
+            move |url| sink.lock().unwrap().push(url.to_string()),
+            true,
+        );
+    let x = CFUserCommand::Info {
+        handles: vec!["thud".to_string()],
+    };
+    assert!(matches!(client.get(&x), Ok(CFResult::CFUserVec(_))));
+    let requests = server.join().unwrap();
+    assert_eq!(requests.len(), 1);
+    assert!(requests[0].contains("/api/user.info?"));
+    let logged = logged.lock().unwrap();
+    assert_eq!(logged.len(), 1);
+    assert!(logged[0].starts_with(&base_url));
+    assert!(logged[0].contains("user.info"));
+    assert!(logged[0].ends_with("apiSig=<redacted>"));
+}
+
+fn test_problem(contest_id: i64, index: &str) -> CFProblem {
+    CFProblem {
+        contest_id: Some(contest_id),
+        problemset_name: None,
+        index: Some(index.to_string()),
+        name: format!("Problem {}", index),
+        problem_type: CFProblemType::Programming,
+        points: None,
+        rating: None,
+        tags: vec![],
+        input_testcases: None,
+    }
+}
+
+fn test_submission(
+    id: i64,
+    problem: CFProblem,
+    verdict: CFSubmissionVerdict,
+    points: Option<f64>,
+) -> CFSubmission {
+    CFSubmission {
+        id,
+        contest_id: problem.contest_id,
+        creation_time_seconds: 1_600_000_000 + id,
+        relative_time_seconds: None,
+        problem,
+        author: test_party(&["thud"], None, false),
+        programming_language: "GNU C++17".to_string(),
+        verdict: Some(verdict),
+        testset: CFTestset::Tests,
+        passed_test_count: 10,
+        time_consumed_millis: 100,
+        memory_consumed_bytes: 1024,
+        points,
+    }
+}
+
+#[test]
+fn test_best_points_per_problem() {
+    let submissions = vec![
+        test_submission(
+            1,
+            test_problem(1000, "A"),
+            CFSubmissionVerdict::Partial,
+            Some(30.0),
+        ),
+        test_submission(
+            2,
+            test_problem(1000, "A"),
+            CFSubmissionVerdict::Partial,
+            Some(70.5),
+        ),
+        test_submission(
+            3,
+            test_problem(1000, "B"),
+            CFSubmissionVerdict::Partial,
+            Some(10.0),
+        ),
+    ];
+    let best = best_points_per_problem(&submissions);
+    assert_eq!(best.len(), 2);
+    assert_eq!(best[&(1000, "A".to_string())], 70.5);
+    assert_eq!(best[&(1000, "B".to_string())], 10.0);
+}
+
+fn test_blog_entry(id: i64, content: Option<&str>) -> CFBlogEntry {
+    CFBlogEntry {
+        id,
+        original_locale: "en".to_string(),
+        creation_time_seconds: 1_600_000_000,
+        author_handle: "thud".to_string(),
+        title: format!("Blog {}", id),
+        content: content.map(|s| s.to_string()),
+        locale: "en".to_string(),
+        modification_time_seconds: 1_600_000_000,
+        allow_view_history: true,
+        tags: vec![],
+        rating: 0,
+    }
+}
+
+#[test]
+fn test_blog_entry_content_or_title() {
+    let entry = test_blog_entry(1, None);
+    assert!(!entry.has_content());
+    assert_eq!(entry.content_or_title(), "Blog 1");
+    let entry = test_blog_entry(2, Some("<p>hello</p>"));
+    assert!(entry.has_content());
+    assert_eq!(entry.content_or_title(), "<p>hello</p>");
+}
+
+#[test]
+fn test_client_connect_timeout() {
+    use crate::client::CFClient;
+    use std::time::{Duration, Instant};
+
+    let (k, s) = get_api_keys();
+    let client =
+        CFClient::new(k, s).with_connect_timeout(Duration::from_millis(200));
+    let start = Instant::now();
+    // 10.255.255.1 is non-routable so connecting should never succeed.
+    match client.send("http://10.255.255.1/") {
+        Err(Error::Http(_)) => {}
+        _ => panic!("Fail, expected Http error from unroutable host."),
+    }
+    assert!(start.elapsed() < Duration::from_secs(5));
+}
+
+#[test]
+fn test_result_variant_name() {
+    assert_eq!(CFResult::CFFriends(vec![]).variant_name(), "CFFriends");
+    assert_eq!(CFResult::CFUserVec(vec![]).variant_name(), "CFUserVec");
+}
+
+#[test]
+fn test_submissions_newer_than() {
+    use crate::client::submissions_newer_than;
+
+    // newest first, ids 10 down to 1.
+    let all: Vec<CFSubmission> = (1..=10)
+        .rev()
+        .map(|id| {
+            test_submission(
+                id,
+                test_problem(1000, "A"),
+                CFSubmissionVerdict::Ok,
+                None,
+            )
+        })
+        .collect();
+    let mut pages_fetched = 0;
+    let new = submissions_newer_than(6, 3, |from, count| {
+        pages_fetched += 1;
+        let start = (from - 1) as usize;
+        let end = (start + count as usize).min(all.len());
+        Ok(all[start..end].to_vec())
+    })
+    .unwrap();
+    let ids: Vec<i64> = new.iter().map(|s| s.id).collect();
+    assert_eq!(ids, vec![10, 9, 8, 7]);
+    assert_eq!(pages_fetched, 2);
+}
+
+fn test_row(
+    party: CFParty,
+    rank: i64,
+    penalty: i64,
+    problem_points: &[f64],
+) -> CFRanklistRow {
+    CFRanklistRow {
+        party,
+        rank,
+        points: problem_points.iter().sum(),
+        penalty,
+        successful_hack_count: 0,
+        unsuccessful_hack_count: 0,
+        problem_results: problem_points
+            .iter()
+            .map(|&points| CFProblemResult {
+                points,
+                penalty: None,
+                rejected_attempt_count: 0,
+                problem_result_type: CFProblemResultType::Final,
+                best_submission_time_seconds: if points > 0.0 {
+                    Some(600)
+                } else {
+                    None
+                },
+            })
+            .collect(),
+        last_submission_time_seconds: None,
+    }
+}
+
+fn test_standings(
+    contest_type: CFContestType,
+    rows: Vec<CFRanklistRow>,
+) -> CFContestStandings {
+    let mut contest = test_contest(1000, CFContestPhase::Finished, None);
+    contest.contest_type = contest_type;
+    let problem_count =
+        rows.first().map_or(0, |r| r.problem_results.len()) as u8;
+    CFContestStandings {
+        contest,
+        problems: (0..problem_count)
+            .map(|i| test_problem(1000, &((b'A' + i) as char).to_string()))
+            .collect(),
+        rows,
+    }
+}
+
+#[test]
+fn test_standings_normalized_scores() {
+    let p = |h: &str| test_party(&[h], None, false);
+    let standings = test_standings(
+        CFContestType::ICPC,
+        vec![
+            test_row(p("third"), 3, 20, &[1.0, 0.0, 0.0]),
+            test_row(p("first"), 1, 300, &[1.0, 1.0, 0.0]),
+            test_row(p("second"), 2, 400, &[1.0, 0.0, 1.0]),
+        ],
+    );
+    let scores = standings.normalized_scores();
+    let mut order: Vec<usize> = (0..scores.len()).collect();
+    order.sort_by(|&a, &b| scores[b].partial_cmp(&scores[a]).unwrap());
+    let ranks: Vec<i64> =
+        order.iter().map(|&i| standings.rows[i].rank).collect();
+    assert_eq!(ranks, vec![1, 2, 3]);
+}
+
+#[cfg(feature = "use_testcase_fetcher")]
+#[test]
+fn test_parse_testcases_invalid_utf8() {
+    let mut page = b"<html><body><div class=\"input\"><pre>1 ".to_vec();
+    page.push(0xff);
+    page.extend_from_slice(b" 2</pre></div></body></html>");
+    let testcases = parse_testcases(&page).unwrap();
+    assert_eq!(testcases, vec!["1 \u{fffd} 2".to_string()]);
+}
+
+#[cfg(feature = "use_testcase_fetcher")]
+#[test]
+fn test_parse_testcases_example_lines() {
+    let page = br#"<html><body>
+        <div class="input"><div class="title">Input</div><pre><div
+            class="test-example-line test-example-line-even test-example-line-0"
+            >2</div><div
+            class="test-example-line test-example-line-odd test-example-line-1"
+            >3 4</div><div
+            class="test-example-line test-example-line-even test-example-line-2"
+            >1 2 3</div></pre></div>
+        <div class="output"><pre>7
+6</pre></div>
+        <div class="input"><div class="title">Input</div><pre><div
+            class="test-example-line test-example-line-even test-example-line-0"
+            >1</div><div
+            class="test-example-line test-example-line-even test-example-line-0"
+            >5 &lt; 6</div></pre></div>
+    </body></html>"#;
+    let testcases = parse_testcases(page).unwrap();
+    assert_eq!(
+        testcases,
+        vec!["2\n3 4\n1 2 3\n".to_string(), "1\n5 < 6\n".to_string()]
+    );
+}
+
+fn test_rating_change(
+    contest_id: i64,
+    rank: i64,
+    time: i64,
+    old_rating: i64,
+    new_rating: i64,
+) -> CFRatingChange {
+    CFRatingChange {
+        contest_id,
+        contest_name: format!("Codeforces Round #{}", contest_id),
+        handle: "thud".to_string(),
+        rank,
+        rating_update_time_seconds: time,
+        old_rating,
+        new_rating,
+    }
+}
+
+#[test]
+fn test_rating_timeline() {
+    let history = vec![
+        test_rating_change(3, 50, 300, 1500, 1550),
+        test_rating_change(1, 100, 100, 0, 1400),
+        test_rating_change(2, 75, 200, 1400, 1500),
+    ];
+    assert_eq!(
+        rating_timeline(&history),
+        vec![(100, 1400), (200, 1500), (300, 1550)]
+    );
+}
+
+#[test]
+fn test_estimated_difficulty() {
+    let mut problem = test_problem(1477, "F");
+    let statistics = CFProblemStatistics {
+        contest_id: Some(1477),
+        index: Some("F".to_string()),
+        solved_count: 12,
+    };
+    assert_eq!(estimated_difficulty(&problem, &statistics), Some(3500));
+    problem.rating = Some(1900);
+    assert_eq!(estimated_difficulty(&problem, &statistics), Some(1900));
+    let other = test_problem(1477, "A");
+    assert_eq!(estimated_difficulty(&other, &statistics), None);
+}
+
+fn test_hack(
+    id: i64,
+    hacker: &str,
+    defender: &str,
+    verdict: CFHackVerdict,
+) -> CFHack {
+    CFHack {
+        id,
+        creation_time_seconds: 1_600_000_000 + id,
+        hacker: test_party(&[hacker], None, false),
+        defender: test_party(&[defender], None, false),
+        verdict: Some(verdict),
+        problem: test_problem(1000, "A"),
+        test: None,
+        judge_protocol: None,
+    }
+}
+
+#[test]
+fn test_hack_tallies() {
+    let hacks = vec![
+        test_hack(1, "alice", "bob", CFHackVerdict::HackSuccessful),
+        test_hack(2, "alice", "carol", CFHackVerdict::HackSuccessful),
+        test_hack(3, "alice", "bob", CFHackVerdict::HackUnsuccessful),
+        test_hack(4, "carol", "bob", CFHackVerdict::HackSuccessful),
+    ];
+    let by_hacker = hacks_by_hacker(&hacks);
+    assert_eq!(by_hacker.len(), 2);
+    assert_eq!(by_hacker["alice"], 2);
+    assert_eq!(by_hacker["carol"], 1);
+    let against = hacks_against(&hacks);
+    assert_eq!(against.len(), 2);
+    assert_eq!(against["bob"], 2);
+    assert_eq!(against["carol"], 1);
+}
+
+#[cfg(feature = "tracing")]
+#[test]
+fn test_tracing_request_span() {
+    use std::sync::{Arc, Mutex};
+    use tracing::field::{Field, Visit};
+    use tracing::span::{Attributes, Id, Record};
+    use tracing::{Event, Metadata, Subscriber};
+
+    // minimal subscriber which records the `method` field of each new span.
+    struct MethodRecorder(Arc<Mutex<Vec<(String, String)>>>);
+    struct MethodVisitor(Option<String>);
+    impl Visit for MethodVisitor {
+        fn record_str(&mut self, field: &Field, value: &str) {
+            if field.name() == "method" {
+                self.0 = Some(value.to_string());
+            }
+        }
+        fn record_debug(&mut self, _: &Field, _: &dyn std::fmt::Debug) {}
+    }
+    impl Subscriber for MethodRecorder {
+        fn enabled(&self, _: &Metadata<'_>) -> bool {
+            true
+        }
+        fn new_span(&self, span: &Attributes<'_>) -> Id {
+            let mut visitor = MethodVisitor(None);
+            span.record(&mut visitor);
+            let mut spans = self.0.lock().unwrap();
+            spans.push((
+                span.metadata().name().to_string(),
+                visitor.0.unwrap_or_default(),
+            ));
+            Id::from_u64(spans.len() as u64)
+        }
+        fn record(&self, _: &Id, _: &Record<'_>) {}
+        fn record_follows_from(&self, _: &Id, _: &Id) {}
+        fn event(&self, _: &Event<'_>) {}
+        fn enter(&self, _: &Id) {}
+        fn exit(&self, _: &Id) {}
+    }
+
+    let (k, s) = get_api_keys();
+    let spans = Arc::new(Mutex::new(Vec::new()));
+    let subscriber = MethodRecorder(Arc::clone(&spans));
+    tracing::subscriber::with_default(subscriber, || {
+        let x = CFBlogEntryCommand::View {
+            blog_entry_id: 82347,
+        };
+        // the span is emitted whether or not the request succeeds.
+        let _ = x.get(k, s);
+    });
+    let spans = spans.lock().unwrap();
+    assert!(spans.contains(&(
+        "codeforces_api_request".to_string(),
+        "blogEntry.view".to_string()
+    )));
+}
+
+#[test]
+fn test_standings_member_ranks() {
+    let standings = test_standings(
+        CFContestType::ICPC,
+        vec![
+            test_row(test_party(&["a", "b"], Some("AB"), false), 1, 0, &[1.0]),
+            test_row(test_party(&["c"], None, false), 2, 0, &[0.0]),
+        ],
+    );
+    let ranks = standings.member_ranks();
+    assert_eq!(ranks.len(), 3);
+    assert_eq!(ranks["a"], 1);
+    assert_eq!(ranks["b"], 1);
+    assert_eq!(ranks["c"], 2);
+}
+
+#[test]
+fn test_client_dry_run() {
+    use crate::client::CFClient;
+
+    let (k, s) = get_api_keys();
+    let client = CFClient::new(k, s).with_dry_run(true);
+    let x = CFUserCommand::Info {
+        handles: vec!["thud".to_string()],
+    };
+    match client.get(&x) {
+        Err(Error::DryRun(url)) => {
+            assert!(url.starts_with("https://codeforces.com/api/user.info?"));
+            assert!(url.contains("handles=thud"));
+            assert!(url.contains("apiSig="));
+        }
+        _ => panic!("Fail, expected dry run error carrying url."),
+    }
+}
+
+#[test]
+fn test_problem_same_problem() {
+    let a = test_problem(1477, "B");
+    let mut b = a.clone();
+    b.input_testcases = Some(vec!["1\n".to_string()]);
+    b.tags = vec!["greedy".to_string()];
+    assert_ne!(a, b);
+    assert!(a.same_problem(&b));
+    assert!(!a.same_problem(&test_problem(1477, "C")));
+}
+
+#[cfg(feature = "use_testcase_fetcher")]
+#[test]
+fn test_parse_contest_materials() {
+    let page = br#"<html><body>
+        <div class="roundbox sidebox">
+            <div class="caption titled">&rarr; Contest materials</div>
+            <ul>
+                <li><a href="/gym/102012/attachments/download/1/statements.pdf">Statements</a></li>
+                <li><a href="https://example.com/tutorial">Tutorial</a></li>
+            </ul>
+        </div>
+        <div class="roundbox sidebox">
+            <div class="caption titled">&rarr; Virtual participation</div>
+            <a href="/gym/102012/virtual">Start</a>
+        </div>
+    </body></html>"#;
+    assert_eq!(
+        parse_contest_materials(page),
+        vec![
+            (
+                "Statements".to_string(),
+                "https://codeforces.com/gym/102012/attachments/download/1/\
+                statements.pdf"
+                    .to_string()
+            ),
+            (
+                "Tutorial".to_string(),
+                "https://example.com/tutorial".to_string()
+            ),
+        ]
+    );
+    assert!(parse_contest_materials(b"<html></html>").is_empty());
+}
+
+#[test]
+fn test_parse_codeforces_api_body() {
+    let ok = r#"{"status":"OK","result":["thud","MikeWazowski"]}"#;
+    match parse_codeforces_api_body(ok) {
+        Ok(CFResult::CFFriends(v)) => assert_eq!(v.len(), 2),
+        _ => panic!("Fail, expected friends list."),
+    }
+    let failed = r#"{"status":"FAILED","comment":"handles: not found"}"#;
+    match parse_codeforces_api_body(failed) {
+        Err(Error::CodeforcesApi(s)) => assert_eq!(s, "handles: not found"),
+        _ => panic!("Fail, expected Codeforces API error."),
+    }
+    match parse_codeforces_api_body("{\"status\":") {
+        Err(Error::Json(_)) => {}
+        _ => panic!("Fail, expected Json error."),
+    }
+}
+
+#[test]
+fn test_submission_is_in_contest() {
+    let mut s = test_submission(
+        1,
+        test_problem(1000, "A"),
+        CFSubmissionVerdict::Ok,
+        None,
+    );
+    assert!(!s.is_in_contest());
+    s.relative_time_seconds = Some(CF_RELATIVE_TIME_SENTINEL);
+    assert!(!s.is_in_contest());
+    s.relative_time_seconds = Some(1234);
+    assert!(s.is_in_contest());
+}
+
+#[cfg(feature = "use_testcase_fetcher")]
+#[test]
+fn test_client_fetch_all_testcases() {
+    use crate::client::CFClient;
+
+    let mut standings = test_standings(CFContestType::Codeforces, vec![]);
+    standings.problems = vec![test_problem(1477, "A"), test_problem(1477, "B")];
+    // `input_testcases` is never returned by the API (and is rejected under
+    // the `strict` feature).
+    let mut result = serde_json::to_value(&standings).unwrap();
+    for p in result["problems"].as_array_mut().unwrap() {
+        p.as_object_mut().unwrap().remove("inputTestcases");
+    }
+    let (base_url, server) = serve_in_turn(vec![
+        (
+            "application/json",
+            format!(r#"{{"status":"OK","result":{}}}"#, result),
+        ),
+        (
+            "text/html",
+            "<div class=\"input\"><pre>1 2</pre></div>".into(),
+        ),
+        ("text/html", "<html><body>No samples.</body></html>".into()),
+    ]);
+    let (k, s) = get_api_keys();
+    let client = CFClient::new(k, s)
+        .with_base_url(&base_url)
+        .unwrap()
+        .with_web_url(base_url.trim_end_matches("api/"))
+        .unwrap();
+    let v = client.fetch_all_testcases(1477).unwrap();
+    assert_eq!(v.len(), 2);
+    assert_eq!(v[0].0, "A");
+    assert_eq!(v[0].1.as_ref().unwrap(), &vec!["1 2".to_string()]);
+    // a failed scrape is reported for its problem only.
+    assert_eq!(v[1].0, "B");
+    match v[1].1 {
+        Err(Error::Testcases(s)) => {
+            assert_eq!(s, "No testcase input found for this problem.")
+        }
+        ref r => panic!("expected a testcases error, got {:?}", r),
+    }
+    let requests = server.join().unwrap();
+    assert!(requests[0].contains("/api/contest.standings?"));
+    assert!(requests[1].starts_with("GET /contest/1477/problem/A "));
+    assert!(requests[2].starts_with("GET /contest/1477/problem/B "));
+}
+
+fn test_comment(id: i64, rating: i64, time: i64) -> CFComment {
+    CFComment {
+        id,
+        creation_time_seconds: time,
+        commentator_handle: "thud".to_string(),
+        locale: "en".to_string(),
+        text: format!("Comment {}", id),
+        parent_comment_id: None,
+        rating,
+    }
+}
+
+#[test]
+fn test_recent_action_kind() {
+    let mut action = CFRecentAction {
+        time_seconds: 1_600_000_000,
+        blog_entry: None,
+        comment: Some(test_comment(1, 0, 1_600_000_000)),
+    };
+    assert_eq!(action.kind(), CFRecentActionKind::NewComment);
+    action.blog_entry = Some(test_blog_entry(1, None));
+    assert_eq!(action.kind(), CFRecentActionKind::Both);
+    action.comment = None;
+    assert_eq!(action.kind(), CFRecentActionKind::NewBlog);
+}
+
+#[test]
+fn test_lenient_points_deserialization() {
+    let problem = |points: &str| {
+        serde_json::from_str::<CFProblem>(&format!(
+            r#"{{"contestId":1477,"index":"B","name":"Nezzar and Binary String",
+            "type":"PROGRAMMING","points":{},"tags":[]}}"#,
+            points
+        ))
+        .unwrap()
+        .points
+    };
+    assert_eq!(problem("100"), Some(100.0));
+    assert_eq!(problem("99.5"), Some(99.5));
+    assert_eq!(problem("\"250\""), Some(250.0));
+    let no_points: CFProblem = serde_json::from_str(
+        r#"{"contestId":1477,"index":"B","name":"Nezzar and Binary String",
+        "type":"PROGRAMMING","tags":[]}"#,
+    )
+    .unwrap();
+    assert_eq!(no_points.points, None);
+    let result: CFProblemResult = serde_json::from_str(
+        r#"{"points":1,"rejectedAttemptCount":0,"type":"FINAL"}"#,
+    )
+    .unwrap();
+    assert_eq!(result.points, 1.0);
+}
+
+#[test]
+fn test_monitor_standings() {
+    use crate::client::monitor_standings;
+    use std::sync::atomic::AtomicBool;
+    use std::time::Duration;
+
+    let p = |h: &str| test_party(&[h], None, false);
+    let mut first = test_standings(
+        CFContestType::Codeforces,
+        vec![
+            test_row(p("a"), 1, 0, &[500.0]),
+            test_row(p("b"), 2, 0, &[0.0]),
+            test_row(p("c"), 3, 0, &[0.0]),
+        ],
+    );
+    first.contest.phase = CFContestPhase::Coding;
+    let second = test_standings(
+        CFContestType::Codeforces,
+        vec![
+            test_row(p("b"), 1, 0, &[1000.0]),
+            test_row(p("a"), 2, 0, &[500.0]),
+            test_row(p("c"), 3, 0, &[0.0]),
+        ],
+    );
+    let mut polls = vec![second, first];
+    let mut reported = vec![];
+    monitor_standings(
+        || Ok(polls.pop().unwrap()),
+        Duration::from_millis(0),
+        &AtomicBool::new(false),
+        |rows| {
+            reported.push(
+                rows.iter()
+                    .map(|r| (r.party.display_name(), r.rank))
+                    .collect::<Vec<_>>(),
+            )
+        },
+    )
+    .unwrap();
+    assert!(polls.is_empty());
+    assert_eq!(
+        reported,
+        vec![vec![("b".to_string(), 1), ("a".to_string(), 2)]]
+    );
+}
+
+#[test]
+fn test_problemset_sorted_by_index() {
+    let sgu = |index: &str| {
+        let mut p = test_problem(0, index);
+        p.contest_id = None;
+        p.problemset_name = Some("acmsguru".to_string());
+        p
+    };
+    let problemset = CFProblemset {
+        problems: vec![sgu("101"), sgu("20"), sgu("3"), sgu("100")],
+        problem_statistics: vec![],
+    };
+    let indices: Vec<&str> = problemset
+        .sorted_by_index()
+        .iter()
+        .map(|p| p.index.as_deref().unwrap())
+        .collect();
+    assert_eq!(indices, vec!["3", "20", "100", "101"]);
+}
+
+#[cfg(feature = "use_testcase_fetcher")]
+#[test]
+fn test_parse_contest_about() {
+    let page = br#"<html><body>
+        <div class="roundbox sidebox">
+            <div class="caption titled">&rarr; About Contest</div>
+            <div>
+                <div>2018-2019 ACM-ICPC Asia Nanjing Regional Contest</div>
+                <div>Prepared by <a href="/profile/Nanako">Nanako</a></div>
+                <div>Website: <a href="https://icpc.example.com/">icpc</a></div>
+            </div>
+        </div>
+    </body></html>"#;
+    let about = parse_contest_about(page);
+    assert_eq!(
+        about.description.as_deref(),
+        Some("2018-2019 ACM-ICPC Asia Nanjing Regional Contest")
+    );
+    assert_eq!(about.prepared_by.as_deref(), Some("Nanako"));
+    assert_eq!(
+        about.website_url.as_deref(),
+        Some("https://icpc.example.com/")
+    );
+}
+
+#[cfg(feature = "use_testcase_fetcher")]
+#[test]
+fn test_fetch_testcase_from_contest_index() {
+    use crate::client::CFClient;
+
+    let mut p = CFProblem::from_contest_index(1477, "B");
+    assert_eq!(p, CFProblem::from((1477, "B")));
+    assert_eq!(p.contest_id, Some(1477));
+    assert_eq!(p.index.as_deref(), Some("B"));
+    assert_eq!(p.input_testcases, None);
+    assert_eq!(
+        p.page_url(WEB_STUB).unwrap(),
+        "https://codeforces.com/contest/1477/problem/B"
+    );
+
+    let page = "<div class=\"input\"><pre>3\n1 2 3\n</pre></div>".to_string();
+    let (base_url, server) = serve_once("text/html", page);
+    let (k, s) = get_api_keys();
+    let client = CFClient::new(k, s)
+        .with_web_url(base_url.trim_end_matches("api/"))
+        .unwrap();
+    let v = client.fetch_testcases(&mut p).unwrap();
+    assert_eq!(v, vec!["3\n1 2 3\n".to_string()]);
+    assert_eq!(p.input_testcases, Some(v));
+    let requests = server.join().unwrap();
+    assert!(requests[0].starts_with("GET /contest/1477/problem/B "));
+}
+
+#[cfg(feature = "use_testcase_fetcher")]
+#[test]
+fn test_client_min_interval_across_api_and_scraping() {
+    use crate::client::CFClient;
+    use std::time::{Duration, Instant};
+
+    let (base_url, server) = serve_in_turn(vec![
+        (
+            "application/json",
+            r#"{"status":"OK","result":["thud"]}"#.into(),
+        ),
+        (
+            "text/html",
+            "<div class=\"input\"><pre>1</pre></div>".into(),
+        ),
+    ]);
+    let (k, s) = get_api_keys();
+    let client = CFClient::new(k, s)
+        .with_base_url(&base_url)
+        .unwrap()
+        .with_web_url(base_url.trim_end_matches("api/"))
+        .unwrap()
+        .with_min_interval(Duration::from_millis(300));
+    let dir = std::env::temp_dir().join("codeforces_api_test_interval");
+    let start = Instant::now();
+    let x = CFUserCommand::Friends { only_online: None };
+    assert!(matches!(client.get(&x), Ok(CFResult::CFFriends(_))));
+    let paths = client
+        .download_testcases_to_dir(
+            &CFProblem::from_contest_index(1477, "B"),
+            &dir,
+        )
+        .unwrap();
+    assert_eq!(paths.len(), 1);
+    assert!(start.elapsed() >= Duration::from_millis(300));
+    std::fs::remove_dir_all(&dir).unwrap();
+    server.join().unwrap();
+}
+
+#[cfg(feature = "use_testcase_fetcher")]
+#[test]
+fn test_client_min_interval_across_api_and_contest_scraping() {
+    use crate::client::CFClient;
+    use std::time::{Duration, Instant};
+
+    let page = "<html><body><div class=\"input\"><pre>1</pre></div>\
+        </body></html>";
+    let (base_url, server) = serve_in_turn(vec![
+        (
+            "application/json",
+            r#"{"status":"OK","result":["thud"]}"#.into(),
+        ),
+        ("text/html", page.to_string()),
+        ("text/html", page.to_string()),
+        ("text/html", page.to_string()),
+    ]);
+    let (k, s) = get_api_keys();
+    let client = CFClient::new(k, s)
+        .with_base_url(&base_url)
+        .unwrap()
+        .with_web_url(base_url.trim_end_matches("api/"))
+        .unwrap()
+        .with_min_interval(Duration::from_millis(300));
+    let start = Instant::now();
+    let x = CFUserCommand::Friends { only_online: None };
+    assert!(matches!(client.get(&x), Ok(CFResult::CFFriends(_))));
+    assert!(client.fetch_contest_materials(1477).unwrap().is_empty());
+    let mut contest = test_contest(1477, CFContestPhase::Finished, None);
+    client.enrich_contest(&mut contest).unwrap();
+    let mut problem = CFProblem::from_contest_index(1477, "B");
+    assert_eq!(client.fetch_testcases(&mut problem).unwrap(), vec!["1"]);
+    assert_eq!(problem.input_testcases, Some(vec!["1".to_string()]));
+    assert!(start.elapsed() >= Duration::from_millis(900));
+    assert_eq!(client.stats().requests, 4);
+    let requests = server.join().unwrap();
+    assert!(requests[1].starts_with("GET /contest/1477 "));
+    assert!(requests[2].starts_with("GET /contest/1477 "));
+    assert!(requests[3].starts_with("GET /contest/1477/problem/B "));
+}
+
+fn test_user(handle: &str, rating: Option<i64>) -> CFUser {
+    CFUser {
+        handle: handle.to_string(),
+        email: None,
+        vk_id: None,
+        open_id: None,
+        first_name: None,
+        last_name: None,
+        country: None,
+        city: None,
+        organization: None,
+        contribution: 0,
+        rank: None,
+        rating,
+        max_rank: None,
+        max_rating: rating,
+        last_online_time_seconds: 1_600_000_000,
+        registration_time_seconds: 1_500_000_000,
+        friend_of_count: 0,
+        avatar: "https://userpic.codeforces.org/no-avatar.jpg".to_string(),
+        title_photo: "https://userpic.codeforces.org/no-title.jpg".to_string(),
+    }
+}
+
+#[test]
+fn test_user_online_status() {
+    let user = test_user("thud", None);
+    let now = user.last_online_time_seconds + 30;
+    assert!(user.is_online_within(5 * 60, now));
+    assert!(user.online_now(now));
+    assert!(!user.is_online_within(10, now));
+    assert!(!user.online_now(now + 3600));
+}
+
+#[test]
+fn test_client_get_all_in_order() {
+    use crate::client::CFClient;
+
+    let (k, s) = get_api_keys();
+    // dry run mode returns each request's url, showing the order they ran.
+    let client = CFClient::new(k, s).with_dry_run(true);
+    let blog = CFBlogEntryCommand::View {
+        blog_entry_id: 82347,
+    };
+    let user = CFUserCommand::Info {
+        handles: vec!["thud".to_string()],
+    };
+    let results = client.get_all(&[&blog, &user]);
+    assert_eq!(results.len(), 2);
+    match (&results[0], &results[1]) {
+        (Err(Error::DryRun(a)), Err(Error::DryRun(b))) => {
+            assert!(a.contains("/blogEntry.view?"));
+            assert!(b.contains("/user.info?"));
+        }
+        _ => panic!("Fail, expected dry run errors carrying urls."),
+    }
+}
+
+#[test]
+fn test_unknown_enum_values() {
+    let verdict: CFSubmissionVerdict =
+        serde_json::from_str("\"SOME_NEW_VERDICT\"").unwrap();
+    assert_eq!(
+        verdict,
+        CFSubmissionVerdict::Unknown("SOME_NEW_VERDICT".to_string())
+    );
+    let verdict: CFSubmissionVerdict =
+        serde_json::from_str("\"WRONG_ANSWER\"").unwrap();
+    assert_eq!(verdict, CFSubmissionVerdict::WrongAnswer);
+    let phase: CFContestPhase = serde_json::from_str("\"POSTPONED\"").unwrap();
+    assert_eq!(phase, CFContestPhase::Unknown("POSTPONED".to_string()));
+    assert_eq!(serde_json::to_string(&phase).unwrap(), "\"POSTPONED\"");
+}
+
+#[test]
+fn test_problem_tags() {
+    let mut p = test_problem(1477, "B");
+    p.tags = vec!["dp".to_string(), "greedy".to_string()];
+    assert!(p.has_tag("DP"));
+    assert!(!p.has_tag("math"));
+    assert!(p.has_all_tags(&["greedy", "dp"]));
+    assert!(!p.has_all_tags(&["greedy", "math"]));
+}
+
+#[test]
+fn test_client_https_only() {
+    use crate::client::CFClient;
+
+    let (k, s) = get_api_keys();
+    let http_only = CFClient::new(k, s)
+        .with_https_only(true)
+        .and_then(|c| c.with_base_url("http://codeforces.com/api/"));
+    match http_only {
+        Err(Error::InvalidParameter(_)) => {}
+        _ => panic!("Fail, expected http base url to be rejected."),
+    }
+    let downgraded = CFClient::new(k, s)
+        .with_base_url("http://codeforces.com/api/")
+        .and_then(|c| c.with_https_only(true));
+    assert!(matches!(downgraded, Err(Error::InvalidParameter(_))));
+    assert!(CFClient::new(k, s)
+        .with_https_only(true)
+        .and_then(|c| c.with_base_url("https://mirror.example.com/api"))
+        .is_ok());
+}
+
+#[test]
+fn test_standings_rank_lookup() {
+    let p = |h| test_party(&[h], None, false);
+    let standings = test_standings(
+        CFContestType::ICPC,
+        vec![
+            test_row(p("first"), 1, 10, &[1.0, 1.0]),
+            test_row(p("tied"), 1, 10, &[1.0, 1.0]),
+            test_row(p("third"), 3, 20, &[1.0, 0.0]),
+        ],
+    );
+    assert_eq!(standings.rank_of("third"), Some(3));
+    assert_eq!(standings.rank_of("Tied"), Some(1));
+    assert_eq!(standings.rank_of("absent"), None);
+    let row = standings.party_at_rank(3).unwrap();
+    assert_eq!(row.party.members[0].handle, "third");
+    assert_eq!(
+        standings.party_at_rank(1).unwrap().party.members[0].handle,
+        "first"
+    );
+    assert!(standings.party_at_rank(2).is_none());
+}
+
+#[test]
+fn test_sign_request() {
+    let (k, s) = get_api_keys();
+    let x = CFUserCommand::Info {
+        handles: vec!["thud".to_string()],
+    };
+    let url = sign_request_with_nonce(&x, k, s, "123456", 1600000000);
+    let (stub, query) = url.split_once('?').unwrap();
+    assert_eq!(stub, "https://codeforces.com/api/user.info");
+    let params: Vec<&str> = query.split('&').collect();
+    assert_eq!(params[0], format!("apiKey={}", k));
+    assert_eq!(params[1], "handles=thud");
+    assert_eq!(params[2], "time=1600000000");
+    let sig = params[3].strip_prefix("apiSig=123456").unwrap();
+    assert_eq!(sig.len(), 128);
+    assert!(sig.chars().all(|c| c.is_ascii_hexdigit()));
+    // same nonce and time must give the same signature.
+    assert_eq!(url, sign_request_with_nonce(&x, k, s, "123456", 1600000000));
+    assert!(sign_request(&x, k, s).contains("apiSig="));
+}
+
+#[test]
+fn test_submission_problem_in() {
+    let mut rated = test_problem(1000, "B");
+    rated.rating = Some(1600);
+    rated.tags = vec!["greedy".to_string()];
+    let problemset = CFProblemset {
+        problems: vec![test_problem(1000, "A"), rated],
+        problem_statistics: vec![],
+    };
+    let s = test_submission(
+        1,
+        test_problem(1000, "B"),
+        CFSubmissionVerdict::Ok,
+        None,
+    );
+    let problem = s.problem_in(&problemset).unwrap();
+    assert_eq!(problem.rating, Some(1600));
+    assert!(problem.has_tag("greedy"));
+    let missing = test_submission(
+        2,
+        test_problem(1001, "B"),
+        CFSubmissionVerdict::Ok,
+        None,
+    );
+    assert!(missing.problem_in(&problemset).is_none());
+}
+
+#[test]
+fn test_parse_api_body_into_custom_type() {
+    #[derive(serde::Deserialize)]
+    struct MinimalUser {
+        handle: String,
+        rating: Option<i64>,
+    }
+
+    let body = r#"{"status":"OK","result":[{"handle":"thud","rating":1500,
+        "someNewField":true}]}"#;
+    let users: Vec<MinimalUser> = parse_codeforces_api_body_into(body).unwrap();
+    assert_eq!(users[0].handle, "thud");
+    assert_eq!(users[0].rating, Some(1500));
+
+    let failed = r#"{"status":"FAILED","comment":"handles: not found"}"#;
+    match parse_codeforces_api_body_into::<Vec<MinimalUser>>(failed) {
+        Err(Error::CodeforcesApi(c)) => assert_eq!(c, "handles: not found"),
+        _ => panic!("Fail, expected CodeforcesApi error."),
+    }
+
+    let no_result = r#"{"status":"OK"}"#;
+    assert!(matches!(
+        parse_codeforces_api_body_into::<Vec<MinimalUser>>(no_result),
+        Err(Error::MissingResult)
+    ));
+}
+
+#[test]
+fn test_rating_leaderboard() {
+    let change = |handle: &str, rank, old, new| CFRatingChange {
+        handle: handle.to_string(),
+        ..test_rating_change(1477, rank, 0, old, new)
+    };
+    let leaderboard = rating_leaderboard(&[
+        change("third", 3, 1500, 1450),
+        change("first", 1, 1400, 1550),
+        change("second", 2, 1600, 1600),
+    ]);
+    let ranks: Vec<i64> = leaderboard.iter().map(|d| d.rank).collect();
+    assert_eq!(ranks, vec![1, 2, 3]);
+    assert_eq!(leaderboard[0].handle, "first");
+    assert_eq!(leaderboard[0].delta, 150);
+    assert_eq!(leaderboard[1].delta, 0);
+    assert_eq!(leaderboard[2].delta, -50);
+    assert_eq!(leaderboard[2].new_rating, 1450);
+}
+
+#[test]
+fn test_error_as_reqwest_timeout() {
+    use crate::client::CFClient;
+    use std::net::TcpListener;
+    use std::time::Duration;
+
+    // a listener which never responds, so that requests to it time out.
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let base_url = format!("http://{}/api/", listener.local_addr().unwrap());
+    let (k, s) = get_api_keys();
+    let client = CFClient::new(k, s)
+        .with_base_url(&base_url)
+        .unwrap()
+        .with_timeout(Duration::from_millis(200));
+    let x = CFUserCommand::Info {
+        handles: vec!["thud".to_string()],
+    };
+    let err = client.get(&x).unwrap_err();
+    assert!(err.as_reqwest().unwrap().is_timeout());
+    assert!(Error::DryRun(String::new()).as_reqwest().is_none());
+    drop(listener);
+}
+
+#[test]
+fn test_standings_project_problems() {
+    let p = |h| test_party(&[h], None, false);
+    let standings = test_standings(
+        CFContestType::ICPC,
+        vec![
+            test_row(p("a"), 1, 10, &[1.0, 1.0, 0.0]),
+            test_row(p("b"), 2, 20, &[1.0, 0.0, 1.0]),
+        ],
+    );
+    let projected = standings.project_problems(&["C"]);
+    assert_eq!(projected.problems.len(), 1);
+    assert_eq!(projected.problems[0].index.as_deref(), Some("C"));
+    for row in &projected.rows {
+        assert_eq!(row.problem_results.len(), 1);
+    }
+    assert_eq!(projected.rows[0].problem_results[0].points, 0.0);
+    assert_eq!(projected.rows[1].problem_results[0].points, 1.0);
+    assert_eq!(projected.rows[1].penalty, 20);
+    assert_eq!(standings.rows[0].problem_results.len(), 3);
+}
+
+#[test]
+fn test_client_get_as_overrides_credentials() {
+    use crate::client::CFClient;
+
+    let (k, s) = get_api_keys();
+    let client = CFClient::new("dummy_key", "dummy_secret").with_dry_run(true);
+    let x = CFUserCommand::Info {
+        handles: vec!["thud".to_string()],
+    };
+    match client.get_as(&x, k, s) {
+        Err(Error::DryRun(url)) => {
+            assert!(url.contains(&format!("apiKey={}", k)));
+            assert!(!url.contains("dummy_key"));
+        }
+        _ => panic!("Fail, expected dry run error carrying url."),
+    }
+    match client.get(&x) {
+        Err(Error::DryRun(url)) => assert!(url.contains("apiKey=dummy_key")),
+        _ => panic!("Fail, expected dry run error carrying url."),
+    }
+}
+
+#[test]
+fn test_enum_all_variants() {
+    let verdicts = CFSubmissionVerdict::all_variants();
+    assert_eq!(verdicts.len(), 17);
+    assert!(verdicts.contains(&CFSubmissionVerdict::Ok));
+    assert!(!verdicts
+        .iter()
+        .any(|v| matches!(v, CFSubmissionVerdict::Unknown(_))));
+    assert_eq!(CFContestPhase::all_variants().len(), 5);
+    assert_eq!(CFParticipantType::all_variants().len(), 5);
+    assert_eq!(CFProblemType::all_variants().len(), 2);
+    assert_eq!(CFHackVerdict::all_variants().len(), 8);
+    assert_eq!(CFProblemResultType::all_variants().len(), 2);
+}
+
+#[cfg(feature = "use_testcase_fetcher")]
+#[test]
+fn test_parse_submissions_page() {
+    let page = br#"<html><body><table class="status-frame-datatable">
+        <tr><th>#</th><th>Problem</th><th>Verdict</th></tr>
+        <tr data-submission-id="103">
+            <td><a href="/contest/1477/problem/A">A - Nezzar and Board</a></td>
+            <td><span class="verdict-accepted">Accepted</span></td>
+        </tr>
+        <tr data-submission-id="102">
+            <td><a href="/contest/1477/problem/B">B - Nezzar and Binary String</a></td>
+            <td><span class="verdict-rejected">Wrong answer on test 2</span></td>
+        </tr>
+        <tr data-submission-id="101">
+            <td><a href="/gym/102012/problem/C">C - Cheap Robot</a></td>
+            <td><span class="verdict-accepted">Accepted</span></td>
+        </tr>
+    </table></body></html>"#;
+    let (ids, solved) = parse_submissions_page(page);
+    assert_eq!(ids, vec![103, 102, 101]);
+    assert_eq!(solved.len(), 2);
+    assert!(solved.contains(&(1477, "A".to_string())));
+    assert!(solved.contains(&(102012, "C".to_string())));
+    assert!(!solved.contains(&(1477, "B".to_string())));
+    let (ids, solved) = parse_submissions_page(b"<html></html>");
+    assert!(ids.is_empty() && solved.is_empty());
+}
+
+#[cfg(feature = "use_testcase_fetcher")]
+#[test]
+fn test_client_fetch_solved_problems_url() {
+    use crate::client::CFClient;
+
+    let (k, s) = get_api_keys();
+    let client = CFClient::new(k, s).with_dry_run(true);
+    match client.fetch_solved_problems("a b/c", 3) {
+        Err(Error::DryRun(url)) => assert_eq!(
+            url,
+            "https://codeforces.com/submissions/a%20b%2Fc/page/1"
+        ),
+        r => panic!("expected a dry run error, got {:?}", r),
+    }
+    assert!(client.fetch_solved_problems("thud", 0).unwrap().is_empty());
+    assert_eq!(client.stats().requests, 0);
+}
+
+#[test]
+fn test_client_standings_for_handles() {
+    use crate::client::CFClient;
+
+    let (k, s) = get_api_keys();
+    let client = CFClient::new(k, s).with_dry_run(true);
+    match client.standings_for_handles(1477, &["thud", "tourist"]) {
+        Err(Error::DryRun(url)) => {
+            assert!(url.contains("contest.standings?"));
+            assert!(url.contains("contestId=1477"));
+            assert!(url.contains("handles=thud;tourist"));
+            assert!(url.contains("showUnofficial=true"));
+        }
+        _ => panic!("Fail, expected dry run error carrying url."),
+    }
+}
+
+#[test]
+fn test_html_response_is_service_unavailable() {
+    use crate::client::CFClient;
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+
+    let page = "<!DOCTYPE html><html><body>Codeforces is temporarily \
+        unavailable.</body></html>";
+    match parse_codeforces_api_body(page) {
+        Err(Error::ServiceUnavailable) => {}
+        _ => panic!("Fail, expected ServiceUnavailable error."),
+    }
+
+    // serve the same page with a 200 status code from a local server.
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let base_url = format!("http://{}/api/", listener.local_addr().unwrap());
+    let server = std::thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut buf = [0; 4096];
+        let _ = stream.read(&mut buf).unwrap();
+        write!(
+            stream,
+            "HTTP/1.1 200 OK\r\nContent-Type: text/html; charset=utf-8\r\n\
+            Content-Length: {}\r\nConnection: close\r\n\r\n{}",
+            page.len(),
+            page
+        )
+        .unwrap();
+    });
+    let (k, s) = get_api_keys();
+    let client = CFClient::new(k, s).with_base_url(&base_url).unwrap();
+    let x = CFUserCommand::Info {
+        handles: vec!["thud".to_string()],
+    };
+    let err = client.get(&x).unwrap_err();
+    assert!(matches!(err, Error::ServiceUnavailable));
+    assert!(err.is_retryable());
+    server.join().unwrap();
+}
+
+#[test]
+fn test_malformed_json_is_json_error_on_every_path() {
+    use crate::client::CFClient;
+
+    let body = || ("application/json", "{\"status\":".to_string());
+    let (base_url, server) =
+        serve_in_turn(vec![body(), body(), body(), body(), body()]);
+    let (k, s) = get_api_keys();
+    let client = CFClient::new(k, s).with_base_url(&base_url).unwrap();
+    let x = CFUserCommand::Friends { only_online: None };
+    assert!(matches!(client.get(&x), Err(Error::Json(_))));
+    assert!(matches!(client.get_with_raw(&x), Err(Error::Json(_))));
+    assert!(matches!(client.get_with_meta(&x), Err(Error::Json(_))));
+    assert!(matches!(
+        client.get_into::<Vec<String>, _>(&x),
+        Err(Error::Json(_))
+    ));
+    let client = client.with_max_response_bytes(1024);
+    assert!(matches!(client.get(&x), Err(Error::Json(_))));
+    server.join().unwrap();
+}
+
+#[test]
+fn test_html_body_under_json_content_type_is_service_unavailable() {
+    use crate::client::CFClient;
+
+    let page = "\n<!DOCTYPE html><html><body>Codeforces is temporarily \
+        unavailable.</body></html>"
+        .to_string();
+    let (base_url, server) = serve_in_turn(vec![
+        ("application/json", page.clone()),
+        ("application/json", page),
+    ]);
+    let (k, s) = get_api_keys();
+    let client = CFClient::new(k, s).with_base_url(&base_url).unwrap();
+    let x = CFUserCommand::Info {
+        handles: vec!["thud".to_string()],
+    };
+    assert!(matches!(client.get(&x), Err(Error::ServiceUnavailable)));
+    assert!(matches!(
+        client.get_with_raw(&x),
+        Err(Error::ServiceUnavailable)
+    ));
+    server.join().unwrap();
+}
+
+#[test]
+fn test_standings_recompute_ranks() {
+    let p = |h| test_party(&[h], None, false);
+    let mut standings = test_standings(
+        CFContestType::ICPC,
+        vec![
+            test_row(p("b"), 7, 50, &[1.0, 0.0]),
+            test_row(p("a"), 3, 40, &[1.0, 1.0]),
+            test_row(p("c"), 12, 90, &[0.0, 1.0]),
+        ],
+    );
+    standings.recompute_ranks();
+    let ranks: Vec<(String, i64)> = standings
+        .rows
+        .iter()
+        .map(|r| (r.party.members[0].handle.clone(), r.rank))
+        .collect();
+    assert_eq!(
+        ranks,
+        vec![
+            ("a".to_string(), 1),
+            ("b".to_string(), 2),
+            ("c".to_string(), 3)
+        ]
+    );
+
+    let mut tied = test_standings(
+        CFContestType::ICPC,
+        vec![
+            test_row(p("a"), 4, 40, &[1.0]),
+            test_row(p("b"), 5, 40, &[1.0]),
+            test_row(p("c"), 9, 10, &[0.0]),
+        ],
+    );
+    tied.recompute_ranks();
+    let ranks: Vec<i64> = tied.rows.iter().map(|r| r.rank).collect();
+    assert_eq!(ranks, vec![1, 1, 3]);
+}
+
+#[test]
+fn test_request_method() {
+    let x = CFUserCommand::Info {
+        handles: vec!["thud".to_string()],
+    };
+    assert_eq!(x.method(), CFMethod::UserInfo);
+    assert_eq!(x.method_name(), "user.info");
+    let y = CFContestCommand::RatingChanges { contest_id: 1477 };
+    assert_eq!(y.method(), CFMethod::ContestRatingChanges);
+    assert_eq!(y.method_name(), "contest.ratingChanges");
+    assert_eq!(
+        CFRecentActionsCommand { max_count: 1 }.method_name(),
+        "recentActions"
+    );
+}
+
+#[test]
+fn test_problemset_distinct_tags() {
+    let tagged = |index, tags: &[&str]| CFProblem {
+        tags: tags.iter().map(|t| t.to_string()).collect(),
+        ..test_problem(1000, index)
+    };
+    let problemset = CFProblemset {
+        problems: vec![
+            tagged("A", &["greedy", "math"]),
+            tagged("B", &["dp", "greedy"]),
+            tagged("C", &[]),
+        ],
+        problem_statistics: vec![],
+    };
+    assert_eq!(problemset.distinct_tags(), vec!["dp", "greedy", "math"]);
+}
+
+#[test]
+fn test_partition_by_participant() {
+    let submission = |id, participant_type| {
+        let mut s = test_submission(
+            id,
+            test_problem(1000, "A"),
+            CFSubmissionVerdict::Ok,
+            None,
+        );
+        s.author.participant_type = participant_type;
+        s
+    };
+    let submissions = vec![
+        submission(1, CFParticipantType::Contestant),
+        submission(2, CFParticipantType::Practice),
+        submission(3, CFParticipantType::Contestant),
+    ];
+    let groups = partition_by_participant(&submissions);
+    assert_eq!(groups.len(), 2);
+    let contest: Vec<i64> = groups[&CFParticipantType::Contestant]
+        .iter()
+        .map(|s| s.id)
+        .collect();
+    assert_eq!(contest, vec![1, 3]);
+    assert_eq!(groups[&CFParticipantType::Practice][0].id, 2);
+    assert!(!groups.contains_key(&CFParticipantType::Virtual));
+}
+
+#[test]
+fn test_query_param_order() {
+    let (k, s) = get_api_keys();
+    let x = CFContestCommand::Standings {
+        contest_id: 1477,
+        from: Some(1),
+        count: Some(5),
+        handles: Some(vec!["thud".to_string(), "tourist".to_string()]),
+        room: Some(3),
+        show_unofficial: Some(true),
+    };
+    let url = sign_request_with_nonce(&x, k, s, "123456", 1600000000);
+    let keys: Vec<&str> = url
+        .split_once('?')
+        .unwrap()
+        .1
+        .split('&')
+        .map(|p| p.split_once('=').unwrap().0)
+        .collect();
+    assert_eq!(
+        keys,
+        vec![
+            "apiKey",
+            "contestId",
+            "count",
+            "from",
+            "handles",
+            "room",
+            "showUnofficial",
+            "time",
+            "apiSig"
+        ]
+    );
+
+    // params sharing a key are ordered by value.
+    let mut params = vec![
+        ("handles", "b".to_string()),
+        ("count", "1".to_string()),
+        ("handles", "a".to_string()),
+    ];
+    params.sort_by(cmp_query_params);
+    assert_eq!(
+        params,
+        vec![
+            ("count", "1".to_string()),
+            ("handles", "a".to_string()),
+            ("handles", "b".to_string())
+        ]
+    );
+}
+
+#[test]
+fn test_client_builder() {
+    use crate::client::CFClient;
+    use std::time::Duration;
+
+    let (k, s) = get_api_keys();
+    let client = CFClient::builder()
+        .credentials(k, s)
+        .timeout(Duration::from_secs(7))
+        .connect_timeout(Duration::from_secs(3))
+        .min_interval(Duration::from_millis(10))
+        .base_url("https://mirror.example.com/api")
+        .https_only(true)
+        .request_logger(|_| {}, true)
+        .dry_run(true)
+        .build()
+        .unwrap();
+    assert_eq!(client.timeout(), Some(Duration::from_secs(7)));
+    let x = CFUserCommand::Info {
+        handles: vec!["thud".to_string()],
+    };
+    match client.get(&x) {
+        Err(Error::DryRun(url)) => {
+            assert!(url.starts_with("https://mirror.example.com/api/user.info?"))
+        }
+        _ => panic!("Fail, expected dry run error carrying url."),
+    }
+
+    let no_credentials = CFClient::builder().build();
+    assert!(matches!(no_credentials, Err(Error::InvalidParameter(_))));
+    let insecure = CFClient::builder()
+        .credentials(k, s)
+        .base_url("http://codeforces.com/api/")
+        .https_only(true)
+        .build();
+    assert!(matches!(insecure, Err(Error::InvalidParameter(_))));
+}
+
+#[test]
+fn test_find_contest_id_by_name() {
+    let history = vec![
+        test_rating_change(1466, 120, 100, 1500, 1550),
+        test_rating_change(1477, 80, 200, 1550, 1620),
+    ];
+    assert_eq!(
+        find_contest_id_by_name(&history, "codeforces round #1477"),
+        Some(1477)
+    );
+    assert_eq!(
+        find_contest_id_by_name(&history, "Codeforces Round #1466"),
+        Some(1466)
+    );
+    assert_eq!(find_contest_id_by_name(&history, "Educational Round"), None);
+}
+
+#[test]
+fn test_submission_pages_stream() {
+    use crate::client::SubmissionPages;
+
+    // newest first, ids 10 down to 1.
+    let all: Vec<CFSubmission> = (1..=10)
+        .rev()
+        .map(|id| {
+            test_submission(
+                id,
+                test_problem(1000, "A"),
+                CFSubmissionVerdict::Ok,
+                None,
+            )
+        })
+        .collect();
+    let mut fetches = 0;
+    let taken: Vec<i64> = SubmissionPages::new(2, |from, count| {
+        fetches += 1;
+        let start = (from - 1) as usize;
+        let end = (start + count as usize).min(all.len());
+        Ok(all[start.min(end)..end].to_vec())
+    })
+    .take(5)
+    .map(|s| s.unwrap().id)
+    .collect();
+    assert_eq!(taken, vec![10, 9, 8, 7, 6]);
+    assert_eq!(fetches, 3);
+
+    // an error mid-stream is yielded once, then the stream ends.
+    let mut pages = SubmissionPages::new(2, |from, _| {
+        if from == 1 {
+            Ok(all[..2].to_vec())
+        } else {
+            Err(Error::CodeforcesApi("Call limit exceeded".to_string()))
+        }
+    });
+    assert_eq!(pages.next().unwrap().unwrap().id, 10);
+    assert_eq!(pages.next().unwrap().unwrap().id, 9);
+    assert!(matches!(pages.next(), Some(Err(Error::CodeforcesApi(_)))));
+    assert!(pages.next().is_none());
+
+    let (k, s) = get_api_keys();
+    let client = crate::client::CFClient::new(k, s);
+    let mut invalid = client.submissions_page_stream(None, None);
+    assert!(matches!(
+        invalid.next(),
+        Some(Err(Error::InvalidParameter(_)))
+    ));
+    assert!(invalid.next().is_none());
+}
+
+#[test]
+fn test_contest_icpc_metadata() {
+    let mut contest = test_contest(102012, CFContestPhase::Finished, None);
+    assert_eq!(contest.icpc_metadata(), None);
+    contest.icpc_region = Some(" Northern Eurasia ".to_string());
+    contest.season = Some("2018-2019".to_string());
+    contest.city = Some("".to_string());
+    let meta = contest.icpc_metadata().unwrap();
+    assert_eq!(meta.region.as_deref(), Some("Northern Eurasia"));
+    assert_eq!(meta.season.as_deref(), Some("2018-2019"));
+    assert_eq!(meta.city, None);
+    assert_eq!(meta.country, None);
+}
+
+/// Serves a single HTTP response with the given content type and body from a
+/// local server, returning its API base url and the server thread.
+fn serve_once(
+    content_type: &'static str,
+    body: String,
+) -> (String, std::thread::JoinHandle<Vec<String>>) {
+    serve_in_turn(vec![(content_type, body)])
+}
+
+/// Serves the given `(content_type, body)` HTTP responses in turn, one per
+/// connection, from a local server, returning its API base url and the
+/// server thread.
+fn serve_in_turn(
+    responses: Vec<(&'static str, String)>,
+) -> (String, std::thread::JoinHandle<Vec<String>>) {
+    serve_raw_in_turn(
+        responses
+            .into_iter()
+            .map(|(content_type, body)| {
+                format!(
+                    "HTTP/1.1 200 OK\r\nContent-Type: {}\r\n\
+                    Content-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    content_type,
+                    body.len(),
+                    body
+                )
+            })
+            .collect(),
+    )
+}
+
+/// Writes the given raw HTTP responses in turn, one per connection, from a
+/// local server, returning its API base url and the server thread. The
+/// thread returns the (head of the) request received on each connection.
+fn serve_raw_in_turn(
+    responses: Vec<String>,
+) -> (String, std::thread::JoinHandle<Vec<String>>) {
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let base_url = format!("http://{}/api/", listener.local_addr().unwrap());
+    let server = std::thread::spawn(move || {
+        let mut requests = vec![];
+        for response in responses {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut buf = [0; 4096];
+            let n = stream.read(&mut buf).unwrap();
+            requests.push(String::from_utf8_lossy(&buf[..n]).into_owned());
+            // the client may hang up early (eg. on oversized responses).
+            let _ = stream.write_all(response.as_bytes());
+        }
+        requests
+    });
+    (base_url, server)
+}
+
+#[test]
+fn test_client_rating_of() {
+    use crate::client::CFClient;
+
+    let (k, s) = get_api_keys();
+    for (user, expected) in [
+        (test_user("thud", Some(1500)), Some(1500)),
+        (test_user("newcomer", None), None),
+    ] {
+        let body = format!(
+            r#"{{"status":"OK","result":[{}]}}"#,
+            serde_json::to_string(&user).unwrap()
+        );
+        let (base_url, server) = serve_once("application/json", body);
+        let client = CFClient::new(k, s).with_base_url(&base_url).unwrap();
+        assert_eq!(client.rating_of(&user.handle).unwrap(), expected);
+        server.join().unwrap();
+    }
+}
+
+#[test]
+fn test_standings_dedup_rows() {
+    let p = |h| test_party(&[h], None, false);
+    let team = |handles: &[&str]| test_party(handles, Some("Team"), false);
+    let first_page = vec![
+        test_row(p("a"), 1, 10, &[1.0]),
+        test_row(team(&["b", "c"]), 2, 20, &[1.0]),
+        test_row(p("d"), 3, 30, &[1.0]),
+    ];
+    let second_page = vec![
+        test_row(p("d"), 3, 30, &[1.0]),
+        test_row(team(&["c", "b"]), 2, 20, &[1.0]),
+        test_row(p("e"), 4, 40, &[0.0]),
+    ];
+    let mut standings = test_standings(
+        CFContestType::ICPC,
+        first_page.into_iter().chain(second_page).collect(),
+    );
+    standings.dedup_rows();
+    let names: Vec<String> = standings
+        .rows
+        .iter()
+        .map(|r| r.party.display_name())
+        .collect();
+    assert_eq!(names, vec!["a", "Team", "d", "e"]);
+
+    let mut virtual_party = p("a");
+    virtual_party.participant_type = CFParticipantType::Virtual;
+    standings.rows.push(test_row(virtual_party, 1, 10, &[1.0]));
+    standings.dedup_rows();
+    assert_eq!(standings.rows.len(), 5);
+}
+
+#[test]
+fn test_standings_fill_problem_ratings() {
+    let p = |h| test_party(&[h], None, false);
+    let mut standings = test_standings(
+        CFContestType::Codeforces,
+        vec![test_row(p("a"), 1, 0, &[500.0, 0.0])],
+    );
+    standings.problems[1].rating = Some(2400);
+    let rated = |index, rating| CFProblem {
+        rating: Some(rating),
+        ..test_problem(1000, index)
+    };
+    let problemset = CFProblemset {
+        problems: vec![rated("A", 800), rated("B", 1200)],
+        problem_statistics: vec![],
+    };
+    assert_eq!(standings.problems[0].rating, None);
+    standings.fill_problem_ratings(&problemset);
+    assert_eq!(standings.problems[0].rating, Some(800));
+    // ratings which are already present are left untouched.
+    assert_eq!(standings.problems[1].rating, Some(2400));
+}
+
+#[test]
+fn test_client_deadline_returns_partial_results() {
+    use crate::client::CFClient;
+    use std::time::{Duration, Instant};
+
+    let body = r#"{"status":"OK","result":["thud"]}"#.to_string();
+    let (base_url, server) = serve_once("application/json", body);
+    let (k, s) = get_api_keys();
+    // the second request would have to wait for the minimum interval past
+    // the deadline, so it fails without waiting.
+    let client = CFClient::new(k, s)
+        .with_base_url(&base_url)
+        .unwrap()
+        .with_min_interval(Duration::from_secs(5))
+        .with_deadline(Instant::now() + Duration::from_millis(500));
+    let x = CFUserCommand::Friends { only_online: None };
+    let started = Instant::now();
+    let results = client.get_all(&[&x, &x, &x]);
+    assert!(started.elapsed() < Duration::from_millis(500));
+    assert!(matches!(results[0], Ok(CFResult::CFFriends(_))));
+    assert!(matches!(results[1], Err(Error::DeadlineExceeded)));
+    assert!(matches!(results[2], Err(Error::DeadlineExceeded)));
+    server.join().unwrap();
+}
+
+#[test]
+fn test_user_absolute_photo_urls() {
+    let mut user = test_user("thud", None);
+    user.avatar = "//example/pic.jpg".to_string();
+    assert_eq!(user.avatar_url(), "https://example/pic.jpg");
+    assert_eq!(
+        user.title_photo_url(),
+        "https://userpic.codeforces.org/no-title.jpg"
+    );
+}
+
+#[test]
+fn test_api_sig_known_vector() {
+    // apiSig is "<nonce>" followed by the hex SHA-512 of
+    // "<nonce>/<method>?<sorted params>#<secret>", computed independently for
+    // the embedded test credentials.
+    const EXPECTED_SIG: &str = "123456\
+        d00aee0f064d35a9848c435a39a7038c7b2b61ec6b23f18e609fc02accba7977\
+        099cc1cf440eae8a9cacd31321dff38832f27459aec6d4a8748ff66e9de9c7f7";
+    let (k, s) = get_api_keys();
+    let x = CFUserCommand::Info {
+        handles: vec!["thud".to_string(), "MikeMirzayanov".to_string()],
+    };
+    let url = sign_request_with_nonce(&x, k, s, "123456", 1600000000);
+    let sig = url.split_once("&apiSig=").unwrap().1;
+    assert_eq!(sig, EXPECTED_SIG);
+}
+
+#[test]
+fn test_client_users_in_rating_range() {
+    use crate::client::CFClient;
+
+    let users: Vec<CFUser> = [1200, 1600, 1900, 2100, 2400]
+        .iter()
+        .enumerate()
+        .map(|(i, &r)| test_user(&format!("user{}", i), Some(r)))
+        .collect();
+    let body = format!(
+        r#"{{"status":"OK","result":{}}}"#,
+        serde_json::to_string(&users).unwrap()
+    );
+    let (base_url, server) = serve_once("application/json", body);
+    let (k, s) = get_api_keys();
+    let client = CFClient::new(k, s).with_base_url(&base_url).unwrap();
+    let band = client.users_in_rating_range(1600, 2100, true).unwrap();
+    assert_eq!(band.len(), 3);
+    for user in band {
+        let rating = user.rating.unwrap();
+        assert!((1600..=2100).contains(&rating));
+    }
+    server.join().unwrap();
+}
+
+#[test]
+fn test_standings_to_markdown() {
+    let p = |h| test_party(&[h], None, false);
+    let standings = test_standings(
+        CFContestType::ICPC,
+        vec![
+            test_row(p("first"), 1, 10, &[1.0, 1.0]),
+            test_row(p("second"), 2, 20, &[1.0, 0.0]),
+            test_row(
+                test_party(&["a", "b"], Some("A|B"), false),
+                3,
+                30,
+                &[1.0, 0.0],
+            ),
+            test_row(p("fourth"), 4, 40, &[0.0, 0.0]),
+        ],
+    );
+    let table = standings.to_markdown(3);
+    let lines: Vec<&str> = table.lines().collect();
+    assert_eq!(lines.len(), 5);
+    assert_eq!(lines[0], "| Rank | Handle | Points | Penalty |");
+    assert_eq!(lines[1], "|---:|---|---:|---:|");
+    assert_eq!(lines[2], "| 1 | first | 2 | 10 |");
+    assert_eq!(lines[4], "| 3 | A\\|B | 1 | 30 |");
+    assert!(!table.contains("fourth"));
+}
+
+#[test]
+fn test_top_comments() {
+    let comments = vec![
+        test_comment(1, 5, 300),
+        test_comment(2, 42, 200),
+        test_comment(3, -3, 100),
+        test_comment(4, 5, 100),
+        test_comment(5, 17, 400),
+    ];
+    let top: Vec<i64> = top_comments(comments.clone(), 3)
+        .iter()
+        .map(|c| c.id)
+        .collect();
+    assert_eq!(top, vec![2, 5, 4]);
+    assert_eq!(top_comments(comments.clone(), 4)[3].id, 1);
+    assert_eq!(top_comments(comments, 10).len(), 5);
+}
+
+#[test]
+fn test_client_stats() {
+    use crate::client::{CFClient, CFClientStats};
+
+    let body = r#"{"status":"OK","result":{"problems":[{"contestId":1000,
+        "index":"A","name":"A","type":"PROGRAMMING","tags":["dp"]}],
+        "problemStatistics":[]}}"#;
+    let (base_url, server) = serve_once("application/json", body.into());
+    let (k, s) = get_api_keys();
+    let client = CFClient::new(k, s).with_base_url(&base_url).unwrap();
+    assert_eq!(client.stats(), CFClientStats::default());
+    assert_eq!(client.known_tags().unwrap(), vec!["dp"]);
+    assert_eq!(client.known_tags().unwrap(), vec!["dp"]);
+    server.join().unwrap();
+    assert_eq!(
+        client.stats(),
+        CFClientStats {
+            requests: 1,
+            cache_hits: 1,
+            retries: 0,
+            failures: 0
+        }
+    );
+
+    // clones share their counters.
+    let failed = r#"{"status":"FAILED","comment":"handles: not found"}"#;
+    let (base_url, server) = serve_once("application/json", failed.into());
+    let other = client.clone().with_base_url(&base_url).unwrap();
+    assert!(other.rating_of("nobody").is_err());
+    server.join().unwrap();
+    let stats = client.stats();
+    assert_eq!((stats.requests, stats.failures), (2, 1));
+}
+
+#[test]
+fn test_languages_used() {
+    let submission = |id, language: &str| CFSubmission {
+        programming_language: language.to_string(),
+        ..test_submission(
+            id,
+            test_problem(1000, "A"),
+            CFSubmissionVerdict::Ok,
+            None,
+        )
+    };
+    let submissions = vec![
+        submission(1, "GNU C++17"),
+        submission(2, "Python 3"),
+        submission(3, "GNU C++17"),
+    ];
+    let languages = languages_used(&submissions);
+    assert_eq!(languages.len(), 2);
+    assert!(languages.contains("Python 3"));
+    assert!(languages_used(&[]).is_empty());
+}
+
+#[test]
+fn test_language_families() {
+    let submission = |id, language: &str| CFSubmission {
+        programming_language: language.to_string(),
+        ..test_submission(
+            id,
+            test_problem(1000, "A"),
+            CFSubmissionVerdict::Ok,
+            None,
+        )
+    };
+    let submissions = vec![
+        submission(1, "GNU C++17"),
+        submission(2, "Clang++20 Diagnostics"),
+        submission(3, "PyPy 3-64"),
+        submission(4, "Python 3"),
+        submission(5, "GNU C11"),
+    ];
+    let families = language_families(&submissions);
+    assert_eq!(
+        families.into_iter().collect::<Vec<_>>(),
+        vec![
+            CFLanguageFamily::C,
+            CFLanguageFamily::Cpp,
+            CFLanguageFamily::Python
+        ]
+    );
+    assert_eq!(submissions[0].language_family(), CFLanguageFamily::Cpp);
+    let family = |s: &str| s.parse::<CFLanguageFamily>().unwrap();
+    assert_eq!(family("Java 21 64bit"), CFLanguageFamily::Java);
+    assert_eq!(family("Node.js 15.8.0"), CFLanguageFamily::JavaScript);
+    assert_eq!(family("C# 10, .NET SDK 6.0"), CFLanguageFamily::CSharp);
+    assert_eq!(family("Rust 1.75.0 (2021)"), CFLanguageFamily::Rust);
+    assert_eq!(family("Go 1.22.2"), CFLanguageFamily::Go);
+    assert_eq!(family("PascalABC.NET 3.8.3"), CFLanguageFamily::Pascal);
+    assert_eq!(family("Free Pascal 3.2.2"), CFLanguageFamily::Pascal);
+    assert_eq!(
+        family("Befunge"),
+        CFLanguageFamily::Other("Befunge".to_string())
+    );
+}
+
+#[test]
+fn test_error_failure_kind() {
+    use crate::client::CFClient;
+
+    let unavailable = Error::CodeforcesApi(
+        "contestId: Rating changes are unavailable for this contest"
+            .to_string(),
+    );
+    assert_eq!(
+        unavailable.failure_kind(),
+        Some(CFFailureKind::RatingChangesUnavailable)
+    );
+    assert!(!unavailable.is_retryable());
+    assert_eq!(
+        Error::CodeforcesApi("handles: User with handle x not found".into())
+            .failure_kind(),
+        Some(CFFailureKind::NotFound)
+    );
+    assert_eq!(
+        Error::CodeforcesApi("Call limit exceeded".into()).failure_kind(),
+        Some(CFFailureKind::CallLimitExceeded)
+    );
+    assert_eq!(Error::DeadlineExceeded.failure_kind(), None);
+
+    // rating changes of a gym contest, as answered by Codeforces.
+    let body = r#"{"status":"FAILED","comment":"contestId: Rating changes are
+        unavailable for this contest"}"#
+        .replace("\n        ", " ");
+    let (base_url, server) = serve_once("application/json", body);
+    let (k, s) = get_api_keys();
+    let client = CFClient::new(k, s).with_base_url(&base_url).unwrap();
+    let x = CFContestCommand::RatingChanges { contest_id: 102012 };
+    let err = client.get(&x).unwrap_err();
+    assert_eq!(
+        err.failure_kind(),
+        Some(CFFailureKind::RatingChangesUnavailable)
+    );
+    server.join().unwrap();
+}
+
+#[test]
+fn test_contest_duration_histogram() {
+    let contest = |id, duration_seconds| CFContest {
+        duration_seconds,
+        ..test_contest(id, CFContestPhase::Finished, None)
+    };
+    let contests = vec![
+        contest(1, 7200),
+        contest(2, 9000),
+        contest(3, 5400),
+        contest(4, 7200),
+        contest(5, 18000),
+    ];
+    let histogram = contest_duration_histogram(&contests);
+    assert_eq!(histogram.get(&2), Some(&3));
+    assert_eq!(histogram.get(&1), Some(&1));
+    assert_eq!(histogram.get(&5), Some(&1));
+    assert_eq!(histogram.get(&3), None);
+}
+
+#[test]
+fn test_user_info_empty_handles() {
+    use crate::client::CFClient;
+
+    let (k, s) = get_api_keys();
+    let x = CFUserCommand::Info { handles: vec![] };
+    match x.get(k, s) {
+        Err(Error::InvalidParameter(msg)) => {
+            assert_eq!(msg, "user.info requires at least one handle")
+        }
+        _ => panic!("Fail, expected InvalidParameter error."),
+    }
+    // validation happens before anything is sent, even in dry-run mode.
+    let client = CFClient::new(k, s).with_dry_run(true);
+    assert!(matches!(client.get(&x), Err(Error::InvalidParameter(_))));
+    assert!(matches!(
+        client.get_raw(&x),
+        Err(Error::InvalidParameter(_))
+    ));
+    assert_eq!(client.stats().requests, 0);
+}
+
+#[test]
+fn test_client_on_retry() {
+    use crate::client::CFClient;
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+
+    let (base_url, server) = serve_in_turn(vec![
+        (
+            "text/html",
+            "<html>Codeforces is unavailable</html>".to_string(),
+        ),
+        (
+            "application/json",
+            r#"{"status":"OK","result":["thud"]}"#.to_string(),
+        ),
+    ]);
+    let calls = Arc::new(Mutex::new(Vec::new()));
+    let sink = Arc::clone(&calls);
+    let (k, s) = get_api_keys();
+    let client = CFClient::new(k, s)
+        .with_base_url(&base_url)
+        .unwrap()
+        .with_retries(2, Duration::from_millis(10))
+        .with_on_retry(move |attempt, err, delay| {
+            sink.lock().unwrap().push((attempt, err.to_string(), delay));
+        });
+    let x = CFUserCommand::Friends { only_online: None };
+    assert!(matches!(client.get(&x), Ok(CFResult::CFFriends(_))));
+    server.join().unwrap();
+    let calls = calls.lock().unwrap();
+    assert_eq!(calls.len(), 1);
+    assert_eq!(calls[0].0, 1);
+    assert_eq!(calls[0].1, Error::ServiceUnavailable.to_string());
+    assert_eq!(calls[0].2, Duration::from_millis(10));
+    let stats = client.stats();
+    assert_eq!((stats.requests, stats.retries, stats.failures), (2, 1, 1));
+}
+
+#[test]
+fn test_standings_solve_counts() {
+    let p = |h| test_party(&[h], None, false);
+    let standings = test_standings(
+        CFContestType::ICPC,
+        vec![
+            test_row(p("a"), 1, 10, &[1.0, 1.0, 0.0]),
+            test_row(p("b"), 2, 20, &[1.0, 0.0, 0.0]),
+            test_row(p("c"), 3, 30, &[1.0, 0.0, 0.0]),
+        ],
+    );
+    let counts = standings.solve_counts();
+    assert_eq!(counts.len(), 3);
+    assert_eq!(counts["A"], 3);
+    assert_eq!(counts["B"], 1);
+    assert_eq!(counts["C"], 0);
+}
+
+#[test]
+fn test_first_accepted_time() {
+    let submission = |id, index, verdict, time| CFSubmission {
+        creation_time_seconds: time,
+        ..test_submission(id, test_problem(1000, index), verdict, None)
+    };
+    let submissions = vec![
+        submission(4, "A", CFSubmissionVerdict::Ok, 400),
+        submission(3, "A", CFSubmissionVerdict::WrongAnswer, 100),
+        submission(2, "A", CFSubmissionVerdict::Ok, 250),
+        submission(1, "B", CFSubmissionVerdict::Ok, 50),
+    ];
+    assert_eq!(first_accepted_time(&submissions, 1000, "A"), Some(250));
+    assert_eq!(first_accepted_time(&submissions, 1000, "B"), Some(50));
+    assert_eq!(first_accepted_time(&submissions, 1000, "C"), None);
+    assert_eq!(first_accepted_time(&submissions, 1001, "A"), None);
+}
+
+#[test]
+fn test_client_get_with_meta() {
+    use crate::client::CFClient;
+
+    let body = r#"{"status":"OK","comment":"user.friends is deprecated",
+        "result":["thud"]}"#;
+    let (base_url, server) = serve_once("application/json", body.into());
+    let (k, s) = get_api_keys();
+    let client = CFClient::new(k, s).with_base_url(&base_url).unwrap();
+    let x = CFUserCommand::Friends { only_online: None };
+    let (result, comment) = client.get_with_meta(&x).unwrap();
+    assert!(matches!(result, CFResult::CFFriends(_)));
+    assert_eq!(comment.as_deref(), Some("user.friends is deprecated"));
+    server.join().unwrap();
+
+    let empty = r#"{"status":"OK","comment":"","result":["thud"]}"#;
+    let (_, comment) = parse_codeforces_api_body_with_comment(empty).unwrap();
+    assert_eq!(comment, None);
+}
+
+#[test]
+fn test_user_rank_enum() {
+    let gm: CFRank = "grandmaster".parse().unwrap();
+    let m: CFRank = "Master".parse().unwrap();
+    assert_eq!(gm, CFRank::Grandmaster);
+    assert!(gm > m);
+    assert!(CFRank::Newbie < CFRank::LegendaryGrandmaster);
+    assert!("headquarters".parse::<CFRank>().is_err());
+    assert_eq!(
+        CFRank::CandidateMaster.as_str().parse::<CFRank>().unwrap(),
+        CFRank::CandidateMaster
+    );
+
+    let mut user: CFUser = serde_json::from_str(
+        r#"{"handle":"thud","contribution":0,"rank":"candidate master",
+        "lastOnlineTimeSeconds":0,"registrationTimeSeconds":0,
+        "friendOfCount":0,"avatar":"","titlePhoto":""}"#,
+    )
+    .unwrap();
+    assert_eq!(user.rank_enum(), Some(CFRank::CandidateMaster));
+    user.rank = None;
+    assert_eq!(user.rank_enum(), None);
+}
+
+#[test]
+fn test_client_sequential_requests() {
+    use crate::client::CFClient;
+    use std::time::Duration;
+
+    let body = r#"{"status":"OK","result":["thud"]}"#.to_string();
+    let (base_url, server) = serve_in_turn(vec![
+        ("application/json", body.clone()),
+        ("application/json", body),
+    ]);
+    let (k, s) = get_api_keys();
+    let client = CFClient::new(k, s)
+        .with_pool_idle_timeout(Duration::from_secs(30))
+        .with_base_url(&base_url)
+        .unwrap();
+    let x = CFUserCommand::Friends { only_online: None };
+    assert!(client.get(&x).is_ok());
+    assert!(client.get(&x).is_ok());
+    server.join().unwrap();
+}
+
+#[test]
+fn test_client_connection_reuse() {
+    use crate::client::CFClient;
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+    use std::time::Duration;
+
+    // accept a single connection and answer every request sent on it, so
+    // that the second request only succeeds if the connection is reused.
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let base_url = format!("http://{}/api/", listener.local_addr().unwrap());
+    let server = std::thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        stream
+            .set_read_timeout(Some(Duration::from_secs(5)))
+            .unwrap();
+        let body = r#"{"status":"OK","result":["thud"]}"#;
+        let mut served = 0;
+        let mut buf = [0; 4096];
+        while let Ok(n) = stream.read(&mut buf) {
+            if n == 0 {
+                break;
+            }
+            served += 1;
+            write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\n\
+                Content-Length: {}\r\n\r\n{}",
+                body.len(),
+                body
+            )
+            .unwrap();
+            if served == 2 {
+                break;
+            }
+        }
+        served
+    });
+    let (k, s) = get_api_keys();
+    let client = CFClient::new(k, s)
+        .with_base_url(&base_url)
+        .unwrap()
+        .with_timeout(Duration::from_secs(10));
+    let x = CFUserCommand::Friends { only_online: None };
+    assert!(matches!(client.get(&x), Ok(CFResult::CFFriends(_))));
+    assert!(matches!(client.get(&x), Ok(CFResult::CFFriends(_))));
+    assert_eq!(server.join().unwrap(), 2);
+}
+
+#[test]
+fn test_problem_difficulty_color() {
+    let mut problem = CFProblem::from_contest_index(1, "A");
+    assert_eq!(problem.difficulty_color(), None);
+    problem.rating = Some(1900);
+    assert_eq!(problem.difficulty_color(), Some(CFRankColor::Violet));
+    problem.rating = Some(1899);
+    assert_eq!(problem.difficulty_color(), Some(CFRankColor::Blue));
+    problem.rating = Some(800);
+    assert_eq!(problem.difficulty_color(), Some(CFRankColor::Gray));
+    assert_eq!(CFRank::CandidateMaster.color(), CFRankColor::Violet);
+}
+
+#[test]
+fn test_recent_actions_command_default() {
+    assert_eq!(
+        CFRecentActionsCommand::default().max_count,
+        DEFAULT_RECENT_ACTIONS_COUNT
+    );
+    assert_eq!(CFRecentActionsCommand::default().max_count, 10);
+    assert_eq!(
+        CFRecentActionsCommand::new(3),
+        CFRecentActionsCommand { max_count: 3 }
+    );
+}
+
+#[test]
+fn test_client_whoami() {
+    use crate::client::CFClient;
+
+    let (k, s) = get_api_keys();
+    let body = format!(
+        r#"{{"status":"OK","result":[{}]}}"#,
+        serde_json::to_string(&test_user("thud", Some(1500))).unwrap()
+    );
+    let (base_url, server) = serve_in_turn(vec![
+        ("application/json", body.clone()),
+        ("application/json", body),
+    ]);
+    let client = CFClient::builder()
+        .credentials(k, s)
+        .handle("thud")
+        .base_url(&base_url)
+        .build()
+        .unwrap();
+    assert_eq!(client.whoami().unwrap().handle, "thud");
+    // a response without the user is reported by the crate, rather than as a
+    // Codeforces failure.
+    let other = client.with_handle("other");
+    assert!(matches!(other.whoami(), Err(Error::InvalidParameter(_))));
+    server.join().unwrap();
+
+    let anonymous = CFClient::new(k, s).with_dry_run(true);
+    assert!(matches!(
+        anonymous.whoami(),
+        Err(Error::InvalidParameter(_))
+    ));
+}
+
+#[test]
+fn test_submission_limit_usage() {
+    let mut submission = test_submission(
+        1,
+        test_problem(1000, "A"),
+        CFSubmissionVerdict::Ok,
+        None,
+    );
+    submission.time_consumed_millis = 900;
+    submission.memory_consumed_bytes = 64 * 1024 * 1024;
+    let usage = submission.limit_usage(1000, 256 * 1024 * 1024);
+    assert!((usage.time - 0.9).abs() < 1e-9);
+    assert!((usage.memory - 0.25).abs() < 1e-9);
+}
+
+#[test]
+fn test_client_find_contests_by_name() {
+    use crate::client::CFClient;
+
+    let mut contests = vec![
+        test_contest(1, CFContestPhase::Finished, None),
+        test_contest(2, CFContestPhase::Finished, None),
+        test_contest(3, CFContestPhase::Finished, None),
+    ];
+    contests[0].name = "Educational Codeforces Round 150".to_string();
+    contests[1].name = "Codeforces Round 880 (Div. 1)".to_string();
+    contests[2].name = "educational Codeforces Round 151".to_string();
+    let body = format!(
+        r#"{{"status":"OK","result":{}}}"#,
+        serde_json::to_string(&contests).unwrap()
+    );
+    let (base_url, server) = serve_once("application/json", body);
+    let (k, s) = get_api_keys();
+    let client = CFClient::new(k, s).with_base_url(&base_url).unwrap();
+    let found = client.find_contests_by_name("EDUCATIONAL", None).unwrap();
+    assert_eq!(found.iter().map(|c| c.id).collect::<Vec<_>>(), vec![1, 3]);
+    server.join().unwrap();
+}
+
+#[test]
+fn test_client_max_response_bytes() {
+    use crate::client::CFClient;
+
+    let body = format!(
+        r#"{{"status":"OK","result":[{}]}}"#,
+        vec![r#""thud""#; 1000].join(",")
+    );
+    let (k, s) = get_api_keys();
+    let x = CFUserCommand::Friends { only_online: None };
+
+    let (base_url, server) = serve_in_turn(vec![
+        ("application/json", body.clone()),
+        ("application/json", body.clone()),
+    ]);
+    let client = CFClient::new(k, s)
+        .with_max_response_bytes(100)
+        .with_base_url(&base_url)
+        .unwrap();
+    assert!(matches!(client.get(&x), Err(Error::ResponseTooLarge(100))));
+    assert!(matches!(
+        client.get_raw(&x),
+        Err(Error::ResponseTooLarge(100))
+    ));
+    server.join().unwrap();
+
+    let (base_url, server) = serve_once("application/json", body.clone());
+    let client = CFClient::new(k, s)
+        .with_max_response_bytes(body.len())
+        .with_base_url(&base_url)
+        .unwrap();
+    assert!(client.get(&x).is_ok());
+    server.join().unwrap();
+
+    // without a `Content-Length`, the body is read until the connection is
+    // closed, so the limit has to be enforced while reading.
+    let unsized_response = format!(
+        "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\n\
+        Connection: close\r\n\r\n{}",
+        body
+    );
+    let (base_url, server) = serve_raw_in_turn(vec![
+        unsized_response.clone(),
+        unsized_response.clone(),
+        unsized_response,
+    ]);
+    let client = CFClient::new(k, s)
+        .with_max_response_bytes(100)
+        .with_base_url(&base_url)
+        .unwrap();
+    assert!(matches!(client.get(&x), Err(Error::ResponseTooLarge(100))));
+    assert!(matches!(
+        client.get_raw(&x),
+        Err(Error::ResponseTooLarge(100))
+    ));
+    let client = client.with_max_response_bytes(body.len());
+    assert_eq!(client.get_raw(&x).unwrap(), body);
+    server.join().unwrap();
+}
+
+#[test]
+fn test_party_kind() {
+    assert_eq!(test_party(&["thud"], None, false).kind(), CFPartyKind::Solo);
+    assert_eq!(
+        test_party(&["a", "b"], Some("Team"), false).kind(),
+        CFPartyKind::Team
+    );
+    assert_eq!(test_party(&["thud"], None, true).kind(), CFPartyKind::Ghost);
+    assert_eq!(
+        test_party(&["a", "b"], Some("Team"), true).kind(),
+        CFPartyKind::Ghost
+    );
+}
+
+#[test]
+fn test_contest_time_until_start() {
+    use std::time::Duration;
+
+    let now = 1_700_000_000;
+    let upcoming = test_contest(1, CFContestPhase::Before, Some(now + 3600));
+    assert_eq!(
+        upcoming.time_until_start(now),
+        Some(Duration::from_secs(60 * 60))
+    );
+    assert_eq!(upcoming.time_until_start(now + 3600), None);
+    let running = test_contest(2, CFContestPhase::Coding, Some(now - 60));
+    assert_eq!(running.time_until_start(now), None);
+    let unknown = test_contest(3, CFContestPhase::Before, None);
+    assert_eq!(unknown.time_until_start(now), None);
+}
+
+#[test]
+fn test_problemset_tag_frequencies() {
+    let tagged = |index, tags: &[&str]| CFProblem {
+        tags: tags.iter().map(|t| t.to_string()).collect(),
+        ..test_problem(1000, index)
+    };
+    let problemset = CFProblemset {
+        problems: vec![
+            tagged("A", &["greedy", "math"]),
+            tagged("B", &["dp", "greedy"]),
+            tagged("C", &[]),
+        ],
+        problem_statistics: vec![],
+    };
+    let frequencies = problemset.tag_frequencies();
+    assert_eq!(frequencies.len(), 3);
+    assert_eq!(frequencies["greedy"], 2);
+    assert_eq!(frequencies["dp"], 1);
+    assert_eq!(frequencies.get("graphs"), None);
+}
+
+#[test]
+fn test_nonce_format() {
+    // ie. `^\d{6}$`.
+    let is_valid =
+        |n: &str| n.len() == 6 && n.bytes().all(|b| b.is_ascii_digit());
+    for _ in 0..1000 {
+        let nonce = generate_nonce();
+        assert!(is_valid(&nonce), "invalid nonce {:?}", nonce);
+    }
+
+    let (k, s) = get_api_keys();
+    let x = CFUserCommand::Info {
+        handles: vec!["thud".to_string()],
+    };
+    for nonce in ["000000", "012345", "000001"] {
+        let url = sign_request_with_nonce(&x, k, s, nonce, 1600000000);
+        let sig = url.split("apiSig=").nth(1).unwrap();
+        assert!(sig.starts_with(nonce));
+        assert_eq!(sig.len(), 6 + 128);
+    }
+}
+
+#[test]
+fn test_standings_rank_deltas() {
+    let p = |h: &str| test_party(&[h], None, false);
+    let earlier = test_standings(
+        CFContestType::Codeforces,
+        vec![
+            test_row(p("a"), 1, 0, &[2.0]),
+            test_row(p("b"), 2, 0, &[1.0]),
+            test_row(p("left"), 3, 0, &[0.0]),
+        ],
+    );
+    let current = test_standings(
+        CFContestType::Codeforces,
+        vec![
+            test_row(p("b"), 1, 0, &[3.0]),
+            test_row(p("a"), 2, 0, &[2.0]),
+            test_row(p("joined"), 3, 0, &[1.0]),
+        ],
+    );
+    let deltas = current.rank_deltas(&earlier);
+    assert_eq!(deltas.len(), 2);
+    assert_eq!(deltas["b"], 1);
+    assert_eq!(deltas["a"], -1);
+    assert!(!deltas.contains_key("joined"));
+    assert!(!deltas.contains_key("left"));
+}
+
+#[test]
+fn test_client_blog_entries_with_content() {
+    use crate::client::CFClient;
+
+    let entry = |id, content: Option<&str>| CFBlogEntry {
+        id,
+        original_locale: "en".to_string(),
+        creation_time_seconds: 1_600_000_000,
+        author_handle: "thud".to_string(),
+        title: format!("Blog {}", id),
+        content: content.map(|c| c.to_string()),
+        locale: "en".to_string(),
+        modification_time_seconds: 1_600_000_000,
+        allow_view_history: true,
+        tags: vec![],
+        rating: 0,
+    };
+    let ok =
+        |result: String| format!(r#"{{"status":"OK","result":{}}}"#, result);
+    let (base_url, server) = serve_in_turn(vec![
+        (
+            "application/json",
+            ok(serde_json::to_string(&[entry(1, None), entry(2, None)])
+                .unwrap()),
+        ),
+        (
+            "application/json",
+            ok(serde_json::to_string(&entry(1, Some("first"))).unwrap()),
+        ),
+        (
+            "application/json",
+            ok(serde_json::to_string(&entry(2, Some("second"))).unwrap()),
+        ),
+    ]);
+    let (k, s) = get_api_keys();
+    let client = CFClient::new(k, s).with_base_url(&base_url).unwrap();
+    let entries = client.blog_entries_with_content("thud").unwrap();
+    assert_eq!(
+        entries
+            .iter()
+            .map(|e| e.content.as_deref())
+            .collect::<Vec<_>>(),
+        vec![Some("first"), Some("second")]
+    );
+    assert_eq!(client.stats().requests, 3);
+    server.join().unwrap();
+}
+
+#[test]
+fn test_client_with_existing_client() {
+    use crate::client::CFClient;
+    use std::time::Duration;
+
+    let body = r#"{"status":"OK","result":["thud"]}"#.to_string();
+    let (base_url, server) = serve_in_turn(vec![
+        ("application/json", body.clone()),
+        ("application/json", body),
+    ]);
+    let http = reqwest::blocking::Client::builder()
+        .user_agent("codeforces-api-test")
+        .build()
+        .unwrap();
+    let (k, s) = get_api_keys();
+    let client = CFClient::with_existing_client(http, k, s)
+        .with_timeout(Duration::from_secs(5))
+        .with_base_url(&base_url)
+        .unwrap();
+    let x = CFUserCommand::Friends { only_online: None };
+    assert!(client.get(&x).is_ok());
+    assert!(client.get_raw(&x).is_ok());
+    assert_eq!(client.stats().requests, 2);
+    for request in server.join().unwrap() {
+        assert!(request
+            .to_lowercase()
+            .contains("user-agent: codeforces-api-test\r\n"));
+    }
+}
+
+#[test]
+fn test_submission_score_summary() {
+    let mut partial = test_submission(
+        1,
+        test_problem(1000, "A"),
+        CFSubmissionVerdict::Partial,
+        Some(42.5),
+    );
+    partial.passed_test_count = 17;
+    let summary = partial.score_summary();
+    assert_eq!(summary.verdict, Some(CFSubmissionVerdict::Partial));
+    assert_eq!(summary.points, Some(42.5));
+    assert_eq!(summary.passed_test_count, 17);
+    assert_eq!(summary.testset, CFTestset::Tests);
+    assert_eq!(summary.pass_ratio, None);
+
+    let accepted = test_submission(
+        2,
+        test_problem(1000, "A"),
+        CFSubmissionVerdict::Ok,
+        None,
+    );
+    assert_eq!(accepted.score_summary().pass_ratio, Some(1.0));
+}
+
+#[test]
+fn test_problemset_random_problem() {
+    let problem = |index, rating, tags: &[&str]| CFProblem {
+        rating,
+        tags: tags.iter().map(|t| t.to_string()).collect(),
+        ..test_problem(1000, index)
+    };
+    let problemset = CFProblemset {
+        problems: vec![
+            problem("A", Some(800), &["greedy"]),
+            problem("B", Some(1400), &["dp", "greedy"]),
+            problem("C", Some(1500), &["dp"]),
+            problem("D", Some(1600), &["dp", "graphs"]),
+            problem("E", None, &["dp"]),
+            problem("F", Some(2400), &["dp"]),
+        ],
+        problem_statistics: vec![],
+    };
+    let pick = problemset.random_problem(&["dp"], 1400, 1600, Some(42));
+    let index = pick.unwrap().index.clone().unwrap();
+    assert!(["B", "C", "D"].contains(&index.as_str()));
+    for _ in 0..10 {
+        assert_eq!(
+            problemset.random_problem(&["dp"], 1400, 1600, Some(42)),
+            pick
+        );
+    }
+    assert_eq!(
+        problemset
+            .random_problem(&["dp", "greedy"], 0, 3500, None)
+            .and_then(|p| p.index.as_deref()),
+        Some("B")
+    );
+    assert_eq!(problemset.random_problem(&["math"], 0, 3500, None), None);
+}
+
+#[test]
+fn test_contest_kind_enum() {
+    let mut contest = test_contest(100001, CFContestPhase::Finished, None);
+    assert_eq!(contest.kind_enum(), None);
+    contest.kind = Some("Training Camp Contest".to_string());
+    assert_eq!(contest.kind_enum(), Some(CFContestKind::TrainingCamp));
+    contest.kind = Some("official ICPC contest".to_string());
+    assert_eq!(contest.kind_enum(), Some(CFContestKind::OfficialIcpc));
+    contest.kind = Some("Mystery Contest".to_string());
+    assert_eq!(
+        contest.kind_enum(),
+        Some(CFContestKind::Other("Mystery Contest".to_string()))
+    );
+}
+
+#[test]
+fn test_poll_recent_actions() {
+    use crate::client::poll_recent_actions;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::time::Duration;
+
+    let action = |id, time| CFRecentAction {
+        time_seconds: time,
+        blog_entry: None,
+        comment: Some(test_comment(id, 0, time)),
+    };
+    // batches are returned newest first, as by `recentActions`.
+    let mut polls = vec![
+        vec![action(4, 300), action(3, 200), action(2, 200)],
+        vec![action(3, 200), action(2, 200), action(1, 100)],
+        vec![action(2, 200), action(1, 100)],
+    ];
+    let stop = AtomicBool::new(false);
+    let mut reported = vec![];
+    poll_recent_actions(
+        || {
+            let batch = polls.remove(polls.len() - 1);
+            if polls.is_empty() {
+                stop.store(true, Ordering::Relaxed);
+            }
+            Ok(batch)
+        },
+        Duration::from_millis(0),
+        &stop,
+        |actions| {
+            reported
+                .extend(actions.iter().map(|a| a.comment.as_ref().unwrap().id))
+        },
+    )
+    .unwrap();
+    assert_eq!(reported, vec![1, 2, 3, 4]);
+}
+
+#[test]
+fn test_problem_parse_index() {
+    let parse = |index: &str| test_problem(1000, index).parse_index();
+    assert_eq!(parse("A1"), Some(('A', Some(1))));
+    assert_eq!(parse("B2"), Some(('B', Some(2))));
+    assert_eq!(parse("C"), Some(('C', None)));
+    assert_eq!(parse("101"), None);
+    assert_eq!(parse("AB"), None);
+    assert_eq!(parse(""), None);
+    let mut no_index = test_problem(1000, "A");
+    no_index.index = None;
+    assert_eq!(no_index.parse_index(), None);
+}
+
+#[test]
+fn test_client_solved_count() {
+    use crate::client::CFClient;
+
+    let submissions = vec![
+        test_submission(
+            1,
+            test_problem(1000, "A"),
+            CFSubmissionVerdict::Ok,
+            None,
+        ),
+        test_submission(
+            2,
+            test_problem(1000, "A"),
+            CFSubmissionVerdict::Ok,
+            None,
+        ),
+        test_submission(
+            3,
+            test_problem(1000, "B"),
+            CFSubmissionVerdict::WrongAnswer,
+            None,
+        ),
+        test_submission(
+            4,
+            test_problem(1001, "A"),
+            CFSubmissionVerdict::Ok,
+            None,
+        ),
+    ];
+    assert_eq!(
+        solved_problems(&submissions),
+        vec![(1000, "A".to_string()), (1001, "A".to_string())]
+            .into_iter()
+            .collect()
+    );
+
+    // `input_testcases` is never returned by the API (and is rejected under
+    // the `strict` feature).
+    let mut result = serde_json::to_value(&submissions).unwrap();
+    for s in result.as_array_mut().unwrap() {
+        s["problem"]
+            .as_object_mut()
+            .unwrap()
+            .remove("inputTestcases");
+    }
+    let body = format!(r#"{{"status":"OK","result":{}}}"#, result);
+    let (base_url, server) = serve_once("application/json", body);
+    let (k, s) = get_api_keys();
+    let client = CFClient::new(k, s).with_base_url(&base_url).unwrap();
+    assert_eq!(client.solved_count("thud").unwrap(), 2);
+    server.join().unwrap();
+}
+
+#[test]
+fn test_cf_result_try_into() {
+    use std::convert::TryInto;
+
+    let result = CFResult::CFUserVec(vec![test_user("thud", Some(1500))]);
+    let users: Vec<CFUser> = result.clone().try_into().unwrap();
+    assert_eq!(users[0].handle, "thud");
+
+    let wrong: Result<Vec<CFSubmission>, Error> = result.try_into();
+    match wrong {
+        Err(Error::UnexpectedResponseType(name)) => {
+            assert_eq!(name, "CFUserVec")
+        }
+        _ => panic!("Fail, expected UnexpectedResponseType error."),
+    }
+}
+
+#[test]
+fn test_client_map_handles() {
+    use crate::client::CFClient;
+    use std::time::{Duration, Instant};
+
+    let body = |user: CFUser| {
+        format!(
+            r#"{{"status":"OK","result":[{}]}}"#,
+            serde_json::to_string(&user).unwrap()
+        )
+    };
+    let (base_url, server) = serve_in_turn(vec![
+        ("application/json", body(test_user("thud", Some(1500)))),
+        ("application/json", body(test_user("other", None))),
+    ]);
+    let (k, s) = get_api_keys();
+    let client = CFClient::new(k, s).with_base_url(&base_url).unwrap();
+    let handles = vec!["thud".to_string(), "other".to_string()];
+    let start = Instant::now();
+    let outcomes = client.map_handles(&handles, |c, h| c.rating_of(h));
+    // without a minimum interval set, requests are still spaced by 2 seconds.
+    assert!(start.elapsed() >= Duration::from_secs(2));
+    assert_eq!(outcomes.len(), 2);
+    assert_eq!(outcomes[0].as_ref().unwrap(), &Some(1500));
+    assert_eq!(outcomes[1].as_ref().unwrap(), &None);
+    server.join().unwrap();
+}
+
+#[test]
+fn test_standings_scorecard_for() {
+    let p = |h: &str| test_party(&[h], None, false);
+    let standings = test_standings(
+        CFContestType::Codeforces,
+        vec![
+            test_row(p("first"), 1, 10, &[500.0, 1000.0, 0.0]),
+            test_row(p("thud"), 2, 20, &[500.0, 0.0, 0.0]),
+        ],
+    );
+    let scorecard = standings.scorecard_for("THUD").unwrap();
+    assert_eq!(scorecard.rank, 2);
+    assert_eq!(scorecard.points, 500.0);
+    assert_eq!(scorecard.penalty, 20);
+    assert_eq!(scorecard.problems.len(), standings.problems.len());
+    assert_eq!(scorecard.problems[0].0, "A");
+    assert_eq!(scorecard.problems[0].1.points, 500.0);
+    assert_eq!(scorecard.problems[1].1.points, 0.0);
+    assert_eq!(standings.scorecard_for("absent"), None);
+}
+
+#[test]
+fn test_parse_malformed_api_responses() {
+    use crate::client::CFClient;
+
+    let failed = r#"{"status":"FAILED"}"#;
+    let no_result = r#"{"status":"OK"}"#;
+    match parse_codeforces_api_body(failed) {
+        Err(Error::CodeforcesApi(c)) => assert_eq!(c, ""),
+        _ => panic!("Fail, expected CodeforcesApi error."),
+    }
+    assert!(matches!(
+        parse_codeforces_api_body(no_result),
+        Err(Error::MissingResult)
+    ));
+
+    let (base_url, server) = serve_in_turn(vec![
+        ("application/json", failed.to_string()),
+        ("application/json", no_result.to_string()),
+    ]);
+    let (k, s) = get_api_keys();
+    let client = CFClient::new(k, s).with_base_url(&base_url).unwrap();
+    let x = CFUserCommand::Friends { only_online: None };
+    assert!(matches!(client.get(&x), Err(Error::CodeforcesApi(_))));
+    assert!(matches!(client.get(&x), Err(Error::MissingResult)));
+    server.join().unwrap();
+}